@@ -2,27 +2,115 @@ use std::collections::HashMap;
 
 use crate::error::Error;
 use proc_macro2::Span;
-use syn::{Expr, Ident, Pat, spanned::Spanned};
+use syn::{Expr, Ident, Pat, Path, Token, punctuated::Punctuated, spanned::Spanned};
 
 pub struct Template {
+    pub capture: Capture,
     pub nodes: Vec<Node>,
 }
 
+/// How the generated closure captures variables, written as `ref;` or `once;` at the
+/// start of a template.
+#[derive(Clone, Copy)]
+pub enum Capture {
+    /// Variables are moved into a closure that can be called many times.
+    Move,
+    /// Variables are borrowed.
+    Ref(Span),
+    /// Variables are moved into a closure that can be called once.
+    Once(Span),
+}
+
 pub enum Node {
     Element(Element),
+    DynamicElement(DynamicElement),
+    Component(Component),
+    Slot(Slot),
+    NamedBlock(NamedBlock),
+    Super(Token![super]),
+    Fragment(Fragment),
     Block(Block),
     Expr(Expr),
     If(If),
     Match(Match),
     ForLoop(ForLoop),
     Let(Let),
+    Error(Error),
+}
+
+impl Template {
+    pub fn contains_fragment(&self) -> bool {
+        self.nodes.iter().any(Node::contains_fragment)
+    }
+
+    /// Returns all fragments, including nested ones, in order of appearance.
+    pub fn fragments(&self) -> Vec<&Fragment> {
+        let mut fragments = Vec::new();
+        self.nodes
+            .iter()
+            .for_each(|node| node.collect_fragments(&mut fragments));
+        fragments
+    }
 }
 
 impl Node {
+    pub fn contains_fragment(&self) -> bool {
+        let mut fragments = Vec::new();
+        self.collect_fragments(&mut fragments);
+        !fragments.is_empty()
+    }
+
+    fn collect_fragments<'a>(&'a self, fragments: &mut Vec<&'a Fragment>) {
+        let mut block = |block: &'a Block| {
+            block
+                .nodes
+                .iter()
+                .for_each(|node| node.collect_fragments(fragments))
+        };
+
+        match self {
+            Node::Fragment(fragment) => {
+                fragments.push(fragment);
+                fragment
+                    .body
+                    .nodes
+                    .iter()
+                    .for_each(|node| node.collect_fragments(fragments));
+            }
+            Node::Element(Element { body, .. })
+            | Node::DynamicElement(DynamicElement { body, .. })
+            | Node::Component(Component { body, .. }) => body.iter().for_each(block),
+            Node::Slot(Slot { body, .. }) | Node::NamedBlock(NamedBlock { body, .. }) => {
+                block(body)
+            }
+            Node::Block(body) | Node::ForLoop(ForLoop { body, .. }) => block(body),
+            Node::If(if_) => {
+                block(&if_.then_branch);
+                if let Some(else_branch) = &if_.else_branch {
+                    else_branch.collect_fragments(fragments);
+                }
+            }
+            Node::Match(match_) => match_
+                .arms
+                .iter()
+                .for_each(|arm| arm.body.collect_fragments(fragments)),
+            Node::Super(_) | Node::Expr(_) | Node::Let(_) | Node::Error(_) => {}
+        }
+    }
+
     pub fn ends_with_eager_brace(&self) -> bool {
         matches!(
             self,
-            Node::Block(_) | Node::Element(_) | Node::ForLoop(_) | Node::If(_) | Node::Match(_)
+            Node::Block(_)
+                | Node::Element(_)
+                | Node::DynamicElement(_)
+                | Node::Component(_)
+                | Node::Slot(_)
+                | Node::NamedBlock(_)
+                | Node::Fragment(_)
+                | Node::ForLoop(_)
+                | Node::If(_)
+                | Node::Match(_)
         )
     }
 }
@@ -43,63 +131,281 @@ impl Element {
         }
     }
 
-    pub fn attributes(&self) -> Result<Vec<(String, Option<Expr>)>, Error> {
+    pub fn validate_attributes(&self) -> Result<(), Error> {
         let mut error = Error::empty();
 
-        let mut key_to_spans = HashMap::new();
-        for (span, key) in self.attr_list.iter().map(Attribute::key) {
-            key_to_spans
-                .entry(key.clone())
-                .or_insert(Vec::new())
-                .push(span);
+        let tag = self.name.to_string();
+        if crate::tags::is_self_closing(&tag).is_some() {
+            for (span, key) in self.attr_list.iter().flat_map(Attribute::keys) {
+                if !crate::tags::is_valid_attribute(&tag, &key) {
+                    error.push(Error::InvalidAttribute {
+                        span,
+                        tag: self.name.clone(),
+                        key,
+                    });
+                }
+            }
         }
 
-        for (key, spans) in key_to_spans
-            .into_iter()
-            .filter(|(_, spans)| spans.len() > 1)
-        {
-            error.push(Error::AttributeSpecifiedMoreThenOnce { spans, key });
+        error.push(duplicate_attributes(&self.attr_list, true));
+
+        if error.is_empty() { Ok(()) } else { Err(error) }
+    }
+}
+
+/// `tag (expr) attributes { ... }`, an element whose name is picked at runtime.
+pub struct DynamicElement {
+    pub name: Expr,
+    pub attr_list: Vec<Attribute>,
+    pub body: Option<Block>,
+}
+
+impl DynamicElement {
+    /// The element isn't known at compile time, so attributes are only checked for duplicates.
+    pub fn validate_attributes(&self) -> Result<(), Error> {
+        let error = duplicate_attributes(&self.attr_list, true);
+        if error.is_empty() { Ok(()) } else { Err(error) }
+    }
+}
+
+pub struct Component {
+    pub path: Path,
+    /// Arguments of the function-call form, `card("Title") { ... }`.
+    pub args: Option<Punctuated<Expr, Token![,]>>,
+    pub props: Vec<Attribute>,
+    pub body: Option<Block>,
+}
+
+impl Component {
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut error = duplicate_attributes(&self.props, false);
+
+        for prop in &self.props {
+            let (span, message) = match prop {
+                Attribute::If(if_) => (if_.if_token.span, CONDITIONAL_PROP),
+                Attribute::Match(match_) => (match_.match_token.span, CONDITIONAL_PROP),
+                Attribute::Class {
+                    cond: Some(cond), ..
+                } => (cond.span(), CONDITIONAL_PROP),
+                Attribute::Spread { dot2_token, .. } => (
+                    dot2_token.spans[0],
+                    "attributes cannot be spread onto a component, pass them as a prop instead",
+                ),
+                _ => continue,
+            };
+            error.push(Error::Syn(syn::Error::new(span, message)));
         }
 
-        if !error.is_empty() {
-            return Err(error);
+        if self.args.is_some() {
+            if let Some(prop) = self.props.first() {
+                let span = prop
+                    .keys()
+                    .first()
+                    .map_or_else(|| self.path.span(), |(span, _)| *span);
+                error.push(Error::Syn(syn::Error::new(
+                    span,
+                    "props cannot be combined with arguments, \
+                     props are only supported by components defined with `#[component]`",
+                )));
+            }
+
+            let named = self.body.iter().flat_map(|body| &body.nodes);
+            for node in named {
+                let (Node::Slot(Slot { name, .. }) | Node::NamedBlock(NamedBlock { name, .. })) =
+                    node
+                else {
+                    continue;
+                };
+                error.push(Error::Syn(syn::Error::new(
+                    name.span(),
+                    "slots and blocks are only supported by components defined with `#[component]`",
+                )));
+            }
         }
 
-        Ok(self
-            .attr_list
-            .iter()
-            .map(|attr| (attr.key().1, attr.value().cloned()))
-            .collect())
+        if error.is_empty() { Ok(()) } else { Err(error) }
+    }
+}
+
+const CONDITIONAL_PROP: &str =
+    "props of a component cannot be conditional, pass an `Option` instead";
+
+/// Reports attributes that may be rendered more then once.
+///
+/// Branches of an `if` or `match` are mutually exclusive, so an attribute may appear
+/// in several of them, but not next to the `if` or `match`. With `merge_classes`, the
+/// `.` shorthands of the element, including those in branches, are merged into one
+/// `class` attribute.
+fn duplicate_attributes(attr_list: &[Attribute], merge_classes: bool) -> Error {
+    let mut key_to_spans = HashMap::new();
+    if merge_classes {
+        let spans: Vec<_> = attr_list.iter().flat_map(Attribute::class_spans).collect();
+        if !spans.is_empty() {
+            key_to_spans.insert("class".to_owned(), (1, spans));
+        }
     }
+
+    duplicate_keys(attr_list, merge_classes, key_to_spans)
+}
+
+fn duplicate_keys(
+    attr_list: &[Attribute],
+    merge_classes: bool,
+    mut key_to_spans: HashMap<String, (usize, Vec<Span>)>,
+) -> Error {
+    let mut error = Error::empty();
+
+    for attr in attr_list {
+        let mut attr_keys: HashMap<String, Vec<Span>> = HashMap::new();
+        for (span, key) in attr.keys_with(!merge_classes) {
+            attr_keys.entry(key).or_default().push(span);
+        }
+
+        for (key, spans) in attr_keys {
+            let (count, all_spans) = key_to_spans.entry(key).or_insert((0, Vec::new()));
+            *count += 1;
+            all_spans.extend(spans);
+        }
+
+        for branch in attr.branches() {
+            error.push(duplicate_keys(branch, merge_classes, HashMap::new()));
+        }
+    }
+
+    for (key, (_, spans)) in key_to_spans
+        .into_iter()
+        .filter(|(_, (count, _))| *count > 1)
+    {
+        error.push(Error::AttributeSpecifiedMoreThenOnce { spans, key });
+    }
+
+    error
+}
+
+/// `slot name { ... }` inside of a component's body.
+pub struct Slot {
+    pub name: Ident,
+    pub body: Block,
+}
+
+/// `block name { ... }` declares an overridable block in a layout, or
+/// overrides it when used inside of a component's body.
+pub struct NamedBlock {
+    pub name: Ident,
+    pub body: Block,
+}
+
+/// `fragment name { ... }`, a part of the template that can be rendered on its own.
+pub struct Fragment {
+    pub name: Ident,
+    pub body: Block,
 }
 
 pub enum Attribute {
     Id(Expr),
-    Class(Expr),
-    KeyValue { key: Ident, value: Option<Expr> },
+    /// `.value` or `.value[cond]`.
+    Class {
+        value: Expr,
+        cond: Option<Expr>,
+    },
+    KeyValue {
+        key: Ident,
+        value: Option<Expr>,
+    },
+    If(AttributeIf),
+    Match(AttributeMatch),
+    /// `..(attrs)`, attributes that are only known at runtime.
+    Spread {
+        dot2_token: Token![..],
+        attrs: Expr,
+    },
 }
 
 impl Attribute {
-    pub fn key(&self) -> (Span, String) {
+    /// Returns the keys of all attributes that may be rendered by `self`.
+    pub fn keys(&self) -> Vec<(Span, String)> {
+        self.keys_with(true)
+    }
+
+    /// Like [`Attribute::keys`], without the `.` shorthands unless `classes` is `true`.
+    fn keys_with(&self, classes: bool) -> Vec<(Span, String)> {
         match self {
-            Self::Id(id) => (id.span(), "id".to_owned()),
-            Self::Class(class) => (class.span(), "class".to_owned()),
-            Self::KeyValue { key, .. } => (
+            Self::Id(id) => vec![(id.span(), "id".to_owned())],
+            Self::Class { value, .. } if classes => vec![(value.span(), "class".to_owned())],
+            Self::Class { .. } => Vec::new(),
+            Self::KeyValue { key, .. } => vec![(
                 key.span(),
                 key.to_string().trim_start_matches("r#").replace("_", "-"),
-            ),
+            )],
+            Self::If(_) | Self::Match(_) => self
+                .branches()
+                .into_iter()
+                .flatten()
+                .flat_map(|attr| attr.keys_with(classes))
+                .collect(),
+            Self::Spread { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the spans of the `.` shorthands in `self`, including those in branches.
+    pub fn class_spans(&self) -> Vec<Span> {
+        match self {
+            Self::Class { value, .. } => vec![value.span()],
+            _ => self
+                .branches()
+                .into_iter()
+                .flatten()
+                .flat_map(Attribute::class_spans)
+                .collect(),
         }
     }
 
-    pub fn value(&self) -> Option<&Expr> {
+    /// Returns `true` if `self` is, or contains, a spread.
+    pub fn contains_spread(&self) -> bool {
+        matches!(self, Self::Spread { .. })
+            || self
+                .branches()
+                .into_iter()
+                .flatten()
+                .any(Attribute::contains_spread)
+    }
+
+    /// Returns the branches of an `if` or `match`.
+    pub fn branches(&self) -> Vec<&[Attribute]> {
         match self {
-            Self::Id(id) => Some(id),
-            Self::Class(class) => Some(class),
-            Self::KeyValue { value, .. } => value.as_ref(),
+            Self::If(if_) => {
+                let mut branches = vec![&if_.then_branch[..]];
+                branches.extend(if_.else_branch.as_deref());
+                branches
+            }
+            Self::Match(match_) => match_.arms.iter().map(|arm| &arm.attrs[..]).collect(),
+            _ => Vec::new(),
         }
     }
 }
 
+/// `if cond { attributes } else { attributes }` in an attribute list.
+pub struct AttributeIf {
+    pub if_token: Token![if],
+    pub cond: Expr,
+    pub then_branch: Vec<Attribute>,
+    /// `else if` is stored as a single [`Attribute::If`].
+    pub else_branch: Option<Vec<Attribute>>,
+}
+
+/// `match expr { pat => { attributes } }` in an attribute list.
+pub struct AttributeMatch {
+    pub match_token: Token![match],
+    pub expr: Expr,
+    pub arms: Vec<AttributeArm>,
+}
+
+pub struct AttributeArm {
+    pub pat: Pat,
+    pub guard: Option<Expr>,
+    pub attrs: Vec<Attribute>,
+}
+
 pub struct Block {
     pub nodes: Vec<Node>,
 }
@@ -0,0 +1,416 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use std::collections::HashSet;
+use syn::{
+    Attribute, Expr, FnArg, GenericParam, Generics, Ident, ItemFn, Lifetime, LifetimeParam, Pat,
+    ReturnType, Type, TypeParam, WherePredicate,
+    ext::IdentExt,
+    parse_quote,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+};
+
+/// Expands `#[component]` on a function into a props struct with a builder.
+///
+/// For `fn card(title: &str, children: impl Render) -> impl Render` this generates
+/// a `Card` struct, with one generic "slot" per parameter. Each slot is either
+/// `Unset`, `UseDefault` or `Set<T>`, and `Card::build` is only callable once
+/// every required slot is `Set`.
+///
+/// Each required prop gets a trait named after it in a hidden module, implemented
+/// only for `Set`, so that a missing prop is reported by name.
+pub fn expand(mut item: ItemFn) -> syn::Result<TokenStream> {
+    if let ReturnType::Default = item.sig.output {
+        return Err(syn::Error::new(
+            item.sig.ident.span(),
+            "components must return a value implementing `Render`",
+        ));
+    }
+
+    let props = item
+        .sig
+        .inputs
+        .iter_mut()
+        .enumerate()
+        .map(|(index, arg)| Prop::new(index, arg))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let vis = &item.vis;
+    let fn_ident = &item.sig.ident;
+    let fn_generics = &item.sig.generics;
+    let name = Ident::new(&pascal_case(&fn_ident.to_string()), fn_ident.span());
+    let doc = format!("Props of the [`{fn_ident}`] component, see [`{name}::builder`].");
+    let required_mod = format_ident!("__{}_required", fn_ident);
+
+    let slots: Vec<_> = (0..props.len()).map(|i| format_ident!("__S{i}")).collect();
+    let fields = props.iter().map(|prop| &prop.ident).collect::<Vec<_>>();
+
+    let initial: Vec<_> = props
+        .iter()
+        .map(|prop| {
+            if prop.default.is_some() {
+                quote!(::gen_html::__private::UseDefault)
+            } else {
+                quote!(::gen_html::__private::Unset)
+            }
+        })
+        .collect();
+
+    let setters = props.iter().enumerate().map(|(index, prop)| {
+        let ident = &prop.ident;
+        let ty = &prop.ty;
+        let generics = prop.generics(fn_generics);
+        let (param, value) = if option_inner(ty).is_some() || prop.region {
+            (
+                quote!(#ident: impl ::std::convert::Into<#ty>),
+                quote!(::std::convert::Into::into(#ident)),
+            )
+        } else {
+            (quote!(#ident: #ty), quote!(#ident))
+        };
+        let output_slots = slots.iter().enumerate().map(|(i, slot)| {
+            if i == index {
+                quote!(::gen_html::__private::Set<#ty>)
+            } else {
+                quote!(#slot)
+            }
+        });
+        let values = fields.iter().enumerate().map(|(i, field)| {
+            if i == index {
+                quote!(#field: ::gen_html::__private::Set(#value))
+            } else {
+                quote!(#field: self.#field)
+            }
+        });
+
+        quote! {
+            #[allow(clippy::type_complexity)]
+            #vis fn #ident #generics(self, #param) -> #name<#(#output_slots),*> {
+                #name { #(#values),* }
+            }
+        }
+    });
+
+    let build_generics = build_generics(fn_generics, &props);
+    let (build_params, _, build_where) = build_generics.split_for_impl();
+    let build_bounds = props.iter().zip(&slots).flat_map(|(prop, slot)| {
+        let ty = &prop.ty;
+        let bound = if prop.default.is_some() {
+            quote!(#slot: ::gen_html::__private::Optional<#ty>)
+        } else {
+            let ident = &prop.ident;
+            quote!(#slot: #required_mod::#ident<#ty>)
+        };
+        let outlives = prop.outlives.iter().map(ToTokens::to_token_stream);
+        std::iter::once(bound).chain(outlives)
+    });
+    let build_args = props.iter().map(|prop| {
+        let ident = &prop.ident;
+        match &prop.default {
+            Some(default) => quote! {
+                ::gen_html::__private::Optional::into_prop_or(self.#ident, || #default)
+            },
+            None => quote!(#required_mod::#ident::into_prop(self.#ident)),
+        }
+    });
+    let captures = build_generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+            GenericParam::Type(param) => param.ident.to_token_stream(),
+            GenericParam::Const(param) => param.ident.to_token_stream(),
+        })
+        .chain(slots.iter().map(ToTokens::to_token_stream));
+    let required = props
+        .iter()
+        .filter(|prop| prop.default.is_none())
+        .map(|prop| {
+            let ident = &prop.ident;
+            let message = format!(
+                "the `{name}` component is missing the required prop `{}`",
+                ident.unraw()
+            );
+            let label = format!("missing `{}`", ident.unraw());
+            quote! {
+                #[diagnostic::on_unimplemented(message = #message, label = #label)]
+                pub trait #ident<T> {
+                    fn into_prop(self) -> T;
+                }
+
+                impl<T> #ident<T> for ::gen_html::__private::Set<T> {
+                    fn into_prop(self) -> T {
+                        self.0
+                    }
+                }
+            }
+        });
+    let build_where = match build_where {
+        Some(clause) => quote!(#clause, #(#build_bounds),*),
+        None => quote!(where #(#build_bounds),*),
+    };
+
+    Ok(quote! {
+        #item
+
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #vis mod #required_mod {
+            #(#required)*
+        }
+
+        #[doc = #doc]
+        #vis struct #name<#(#slots),*> {
+            #(#fields: #slots),*
+        }
+
+        impl #name<#(#initial),*> {
+            /// Creates a builder with all props unset.
+            #vis fn builder() -> Self {
+                #name { #(#fields: #initial),* }
+            }
+        }
+
+        impl<#(#slots),*> #name<#(#slots),*> {
+            #(#setters)*
+
+            /// Renders the component with the given props.
+            #vis fn build #build_params(self)
+                -> impl ::gen_html::Render + use<#(#captures),*>
+            #build_where
+            {
+                #fn_ident(#(#build_args),*)
+            }
+        }
+    })
+}
+
+struct Prop {
+    ident: Ident,
+    /// The parameter type with `impl Trait` and elided lifetimes replaced by
+    /// the generic parameters in `extra`.
+    ty: Type,
+    extra: Vec<GenericParam>,
+    /// Bounds implied by the references in `ty`, eg. `T: 'a` for `&'a T`.
+    outlives: Vec<WherePredicate>,
+    default: Option<Expr>,
+    /// `true` for slots and blocks, which are filled using `slot name { ... }` and
+    /// `block name { ... }` instead of a prop.
+    region: bool,
+}
+
+impl Prop {
+    fn new(index: usize, arg: &mut FnArg) -> syn::Result<Self> {
+        let arg = match arg {
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "components cannot have a `self` parameter",
+                ));
+            }
+            FnArg::Typed(arg) => arg,
+        };
+
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new(
+                arg.pat.span(),
+                "component parameters must be identifiers",
+            ));
+        };
+
+        let PropOptions {
+            mut default,
+            region,
+        } = take_options(&mut arg.attrs)?;
+        if default.is_none() && (option_inner(&arg.ty).is_some() || region) {
+            default = Some(parse_quote!(::std::default::Default::default()));
+        }
+
+        let mut normalize = Normalize {
+            prefix: index,
+            extra: Vec::new(),
+            outlives: Vec::new(),
+        };
+        let mut ty = (*arg.ty).clone();
+        normalize.visit_type_mut(&mut ty);
+
+        // `Slot` and `Block` have a hidden lifetime parameter.
+        if region
+            && let Type::Path(path) = &mut ty
+            && let Some(last) = path.path.segments.last_mut()
+            && last.arguments.is_none()
+        {
+            let lifetime = normalize.lifetime();
+            last.arguments = syn::PathArguments::AngleBracketed(parse_quote!(<#lifetime>));
+        }
+
+        Ok(Self {
+            ident: pat.ident.clone(),
+            ty,
+            extra: normalize.extra,
+            outlives: normalize.outlives,
+            default,
+            region,
+        })
+    }
+
+    /// Generics of this prop's setter: the generated ones, and those of the
+    /// function that are used by the parameter type.
+    fn generics(&self, fn_generics: &Generics) -> Generics {
+        let used = idents(self.ty.to_token_stream());
+        let mut params: Vec<GenericParam> = fn_generics
+            .params
+            .iter()
+            .filter(|param| match param {
+                GenericParam::Lifetime(param) => used.contains(&param.lifetime.ident.to_string()),
+                GenericParam::Type(param) => used.contains(&param.ident.to_string()),
+                GenericParam::Const(param) => used.contains(&param.ident.to_string()),
+            })
+            .cloned()
+            .collect();
+        params.extend(self.extra.iter().cloned());
+        sort_generics(&mut params);
+
+        parse_quote!(<#(#params),*>)
+    }
+}
+
+fn build_generics(fn_generics: &Generics, props: &[Prop]) -> Generics {
+    let mut generics = fn_generics.clone();
+    let mut params: Vec<_> = generics.params.into_iter().collect();
+    params.extend(props.iter().flat_map(|prop| prop.extra.iter().cloned()));
+    sort_generics(&mut params);
+    generics.params = params.into_iter().collect();
+    generics
+}
+
+fn sort_generics(params: &mut [GenericParam]) {
+    params.sort_by_key(|param| !matches!(param, GenericParam::Lifetime(_)));
+}
+
+/// Options of a parameter, written as `#[prop(...)]`.
+#[derive(Default)]
+struct PropOptions {
+    default: Option<Expr>,
+    region: bool,
+}
+
+/// Removes `#[prop(default)]`, `#[prop(default = expr)]`, `#[prop(slot)]` and
+/// `#[prop(block)]` from `attrs`.
+fn take_options(attrs: &mut Vec<Attribute>) -> syn::Result<PropOptions> {
+    let mut options = PropOptions::default();
+    let mut result = Ok(());
+
+    attrs.retain(|attr| {
+        if !attr.path().is_ident("prop") {
+            return true;
+        }
+
+        result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                options.default = Some(if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse()?
+                } else {
+                    parse_quote!(::std::default::Default::default())
+                });
+                Ok(())
+            } else if meta.path.is_ident("slot") || meta.path.is_ident("block") {
+                options.region = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown prop option, expected `default`, `slot` or `block`"))
+            }
+        });
+        false
+    });
+
+    result.map(|_| options)
+}
+
+/// Returns `T` if `ty` is written as `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Replaces `impl Trait` and elided lifetimes with named generic parameters.
+struct Normalize {
+    prefix: usize,
+    extra: Vec<GenericParam>,
+    outlives: Vec<WherePredicate>,
+}
+
+impl Normalize {
+    fn lifetime(&mut self) -> Lifetime {
+        let name = format!("'__l{}_{}", self.prefix, self.extra.len());
+        let lifetime = Lifetime::new(&name, Span::call_site());
+        self.extra
+            .push(GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+        lifetime
+    }
+}
+
+impl VisitMut for Normalize {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        visit_mut::visit_type_mut(self, ty);
+
+        if let Type::ImplTrait(impl_trait) = ty {
+            let ident = format_ident!("__T{}_{}", self.prefix, self.extra.len());
+            let bounds = &impl_trait.bounds;
+            let param: TypeParam = parse_quote!(#ident: #bounds);
+            self.extra.push(GenericParam::Type(param));
+            *ty = parse_quote!(#ident);
+        }
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.lifetime());
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+
+        let (elem, lifetime) = (&reference.elem, &reference.lifetime);
+        self.outlives.push(parse_quote!(#elem: #lifetime));
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.lifetime();
+        }
+    }
+}
+
+fn idents(tokens: TokenStream) -> HashSet<String> {
+    let mut idents = HashSet::new();
+    for tt in tokens {
+        match tt {
+            TokenTree::Ident(ident) => _ = idents.insert(ident.to_string()),
+            TokenTree::Group(group) => idents.extend(self::idents(group.stream())),
+            _ => {}
+        }
+    }
+    idents
+}
+
+fn pascal_case(s: &str) -> String {
+    s.trim_start_matches("r#")
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
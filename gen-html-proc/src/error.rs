@@ -1,3 +1,4 @@
+use crate::tags;
 use proc_macro2::Span;
 use quote::{ToTokens, quote_spanned};
 use syn::Ident;
@@ -7,7 +8,13 @@ pub enum Error {
     InvalidTag(Ident),
     SelfClosing(Ident),
     NotSelfClosing(Ident),
+    SlotOutsideComponent(Ident),
+    SuperOutsideBlock(Span),
+    FragmentNotAllowed(Ident),
+    NotStatic(Span),
+    InvalidAttribute { span: Span, tag: Ident, key: String },
     AttributeSpecifiedMoreThenOnce { spans: Vec<Span>, key: String },
+    Syn(syn::Error),
     Multi(Vec<Error>),
 }
 
@@ -21,6 +28,10 @@ impl Error {
     }
 
     pub fn push(&mut self, other: Self) {
+        if other.is_empty() {
+            return;
+        }
+
         if let Self::Multi(v) = self {
             v.push(other);
         } else {
@@ -33,23 +44,68 @@ impl ToTokens for Error {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         match self {
             Self::InvalidTag(tag) => {
-                let msg = format!("`{tag}` is not a valid HTML tag");
+                let mut msg = format!("`{tag}` is not a valid HTML tag");
+                if let Some(suggestion) = tags::suggest_tag(&tag.to_string()) {
+                    msg += &format!("\n\nhelp: did you mean `{suggestion}`?");
+                }
                 quote_spanned!(tag.span() => compile_error!(#msg);).to_tokens(tokens);
             }
             Self::SelfClosing(tag) => {
-                let msg = format!("`{tag}` must be used like a self closing tag");
+                let msg = format!(
+                    "`{tag}` must be used like a self closing tag\n\n\
+                     note: `{tag}` is a void element, it cannot have any children\n\
+                     help: replace the curly braces with a semicolon: `{tag};`"
+                );
                 quote_spanned!(tag.span() => compile_error!(#msg);).to_tokens(tokens);
             }
             Self::NotSelfClosing(tag) => {
-                let msg = format!("`{tag}` cannot be used like a self closing tag");
+                let msg = format!(
+                    "`{tag}` cannot be used like a self closing tag\n\n\
+                     note: only void elements, like `br` or `img`, may omit the closing tag\n\
+                     help: replace the semicolon with curly braces: `{tag} {{}}`"
+                );
                 quote_spanned!(tag.span() => compile_error!(#msg);).to_tokens(tokens);
             }
+            Self::SlotOutsideComponent(name) => {
+                let msg = format!(
+                    "slot `{name}` is not inside of a component\n\n\
+                     note: slots fill named regions of components, eg. `Layout {{ slot {name} {{ ... }} }}`"
+                );
+                quote_spanned!(name.span() => compile_error!(#msg);).to_tokens(tokens);
+            }
+            Self::SuperOutsideBlock(span) => {
+                let msg = "`super` can only be used inside of a block override\n\n\
+                     note: `super` renders the layout's content of the overridden block";
+                quote_spanned!(*span => compile_error!(#msg);).to_tokens(tokens);
+            }
+            Self::FragmentNotAllowed(name) => {
+                let msg = format!(
+                    "fragments cannot be used inside of `{name}`\n\n\
+                     note: the content of `{name}` is rendered by other code, so it cannot be selected on its own\n\
+                     help: move the fragment around `{name}`"
+                );
+                quote_spanned!(name.span() => compile_error!(#msg);).to_tokens(tokens);
+            }
+            Self::NotStatic(span) => {
+                let msg = "`html_static!` templates cannot contain dynamic content\n\n\
+                     note: only elements, attributes and text written as literals are static\n\
+                     help: use `html!` instead";
+                quote_spanned!(*span => compile_error!(#msg);).to_tokens(tokens);
+            }
+            Self::InvalidAttribute { span, tag, key } => {
+                let mut msg = format!("`{key}` is not a valid attribute of `{tag}`");
+                if let Some(suggestion) = tags::suggest_attribute(&tag.to_string(), key) {
+                    msg += &format!("\n\nhelp: did you mean `{suggestion}`?");
+                }
+                quote_spanned!(*span => compile_error!(#msg);).to_tokens(tokens);
+            }
             Self::AttributeSpecifiedMoreThenOnce { spans, key: name } => {
                 let msg = format!("attribute `{name}` specified more then once");
                 for span in spans {
                     quote_spanned!(*span => compile_error!(#msg);).to_tokens(tokens);
                 }
             }
+            Self::Syn(err) => err.to_compile_error().to_tokens(tokens),
             Self::Multi(errors) => errors.iter().for_each(|e| e.to_tokens(tokens)),
         }
     }
@@ -1,38 +1,217 @@
 use crate::{
-    ast::{Block, Element, ForLoop, If, Let, Match, Node, Template},
+    ast::{
+        Attribute, AttributeIf, AttributeMatch, Block, Capture, Component, DynamicElement, Element,
+        ForLoop, Fragment, If, Let, Match, NamedBlock, Node, Template,
+    },
     error::Error,
 };
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned};
+use std::{cell::Cell, rc::Rc};
 use syn::{
-    Expr, ExprBlock, ExprForLoop, ExprIf, ExprMatch, Ident, Local, LocalInit, Stmt, Token,
-    parse_quote, spanned::Spanned, token::Brace,
+    Expr, ExprBlock, ExprForLoop, ExprIf, ExprLit, ExprMatch, Ident, Lit, LitStr, Local, LocalInit,
+    PatIdent, Stmt, Token, parse_quote,
+    spanned::Spanned,
+    token::Brace,
+    visit_mut::{self, VisitMut},
 };
 
 impl Template {
     pub fn expand(self) -> TokenStream {
         let output_ident = Ident::new("__f", Span::call_site());
-        let block = self.finish(output_ident.clone());
+        let mut ctx = Context::new(output_ident.clone());
+        ctx.reusable = !matches!(self.capture, Capture::Once(_));
+        let move_ = match self.capture {
+            Capture::Ref(_) => None,
+            Capture::Move | Capture::Once(_) => Some(<Token![move]>::default()),
+        };
 
+        if !self.contains_fragment() {
+            let mut body_ctx = ctx.child();
+            self.generate(&mut body_ctx);
+
+            let render_fn = match self.capture {
+                Capture::Once(_) => quote!(::gen_html::render_once),
+                Capture::Move | Capture::Ref(_) => quote!(::gen_html::__private::output_fn),
+            };
+            let size_hint = body_ctx.size_hint.get();
+            let block = body_ctx.finish();
+            return quote! {
+                #render_fn(#move_ |#output_ident: &mut dyn ::gen_html::Output| {
+                    #block
+                    ::std::fmt::Result::Ok(())
+                })
+                .with_size_hint(#size_hint)
+            };
+        }
+
+        // Every fragment renders the template again, so it can't be consumed.
+        if let Capture::Once(span) = self.capture {
+            let err = syn::Error::new(span, "templates with fragments cannot be `once`");
+            ctx.push(Part::Error(Error::Syn(err)));
+        }
+
+        let mut names: Vec<_> = self
+            .fragments()
+            .into_iter()
+            .map(|fragment| fragment.name.to_string())
+            .collect();
+        names.sort();
+        names.dedup();
+
+        // Output outside of the selected fragment is skipped.
+        ctx.fragments = true;
+        let block = self.finish(&ctx);
+        let size_hint = ctx.size_hint.get();
+        let error = ctx.finish();
         quote! {
-            ::gen_html::render_fn(move |#output_ident: &mut ::std::fmt::Formatter| {
+            ::gen_html::fragment_fn(&[#(#names),*], #move_ |
+                #output_ident: &mut dyn ::gen_html::Output,
+                __target: ::std::option::Option<&str>,
+            | {
+                #error
+                let __active = ::std::cell::Cell::new(__target.is_none());
                 #block
                 ::std::fmt::Result::Ok(())
             })
+            .with_size_hint(#size_hint)
+        }
+    }
+
+    /// Expands a template whose expressions may use `?`, see `try_html!`.
+    ///
+    /// The template is rendered right away, inside of a closure returning
+    /// `Result<(), E>`. Write errors are converted with `E: From<fmt::Error>`.
+    pub fn expand_try(self) -> TokenStream {
+        let output = Ident::new("__f", Span::call_site());
+        let mut ctx = Context::new(output.clone());
+        self.reject_capture(&mut ctx, "try_html!", false);
+
+        if self.contains_fragment() {
+            let err = syn::Error::new(
+                Span::call_site(),
+                "fragments cannot be used inside of `try_html!`",
+            );
+            ctx.push(Part::Error(Error::Syn(err)));
+        }
+
+        self.generate(&mut ctx);
+        let size_hint = ctx.size_hint.get();
+        let block = ctx.finish();
+        quote! {
+            ::gen_html::__private::try_render(
+                #size_hint,
+                |#output: &mut dyn ::gen_html::Output| -> ::std::result::Result<(), _> {
+                    #block
+                    ::std::result::Result::Ok(())
+                },
+            )
+        }
+    }
+
+    /// Expands a template whose expressions may use `.await`, see `html_async!`.
+    ///
+    /// The template is rendered by an async closure appending to a `String`, because a
+    /// `&mut dyn Output` isn't `Send` and cannot be held across an `.await`.
+    pub fn expand_async(self) -> TokenStream {
+        let output = Ident::new("__buf", Span::call_site());
+        let mut ctx = Context::new(output.clone());
+        self.reject_capture(&mut ctx, "html_async!", true);
+
+        if self.contains_fragment() {
+            let err = syn::Error::new(
+                Span::call_site(),
+                "fragments cannot be used inside of `html_async!`",
+            );
+            ctx.push(Part::Error(Error::Syn(err)));
+        }
+
+        self.generate(&mut ctx);
+        let size_hint = ctx.size_hint.get();
+        let block = ctx.finish();
+        let move_ = match self.capture {
+            Capture::Ref(_) => None,
+            Capture::Move | Capture::Once(_) => Some(<Token![move]>::default()),
+        };
+        quote! {
+            ::gen_html::async_render_fn(async #move_ |#output: &mut ::std::string::String| {
+                #block
+                ::std::fmt::Result::Ok(())
+            })
+            .with_size_hint(#size_hint)
+        }
+    }
+
+    /// Expands a template that must be static, see `html_static!`.
+    pub fn expand_static(self) -> TokenStream {
+        let mut ctx = Context::new(Ident::new("__f", Span::call_site()));
+        self.reject_capture(&mut ctx, "html_static!", false);
+        self.generate(&mut ctx);
+
+        if let Some(html) = ctx.as_static() {
+            return quote!(::gen_html::Raw(#html));
+        }
+
+        let mut error = Error::empty();
+        for part in &ctx.parts {
+            if let Part::Error(err) = part {
+                error.push(err.clone());
+            }
         }
+        if error.is_empty() {
+            let span = ctx
+                .parts
+                .iter()
+                .find_map(|part| match part {
+                    Part::Render(expr) => Some(expr.span()),
+                    Part::Attribute { value, .. } => Some(value.span()),
+                    Part::Stmt(stmt) => Some(stmt.span()),
+                    Part::Raw(_) | Part::Error(_) => None,
+                })
+                .unwrap_or_else(Span::call_site);
+            error = Error::NotStatic(span);
+        }
+
+        quote!({ #error ::gen_html::Raw("") })
+    }
+
+    /// Reports `ref;` or `once;` at the start of a template passed to `macro_name`,
+    /// which doesn't support it.
+    fn reject_capture(&self, ctx: &mut Context, macro_name: &str, allow_ref: bool) {
+        let (keyword, span) = match self.capture {
+            Capture::Move => return,
+            Capture::Ref(_) if allow_ref => return,
+            Capture::Ref(span) => ("ref", span),
+            Capture::Once(span) => ("once", span),
+        };
+
+        let msg = format!("`{keyword};` cannot be used inside of `{macro_name}`");
+        ctx.push(Part::Error(Error::Syn(syn::Error::new(span, msg))));
     }
 }
 
 trait Build {
     fn generate(&self, ctx: &mut Context);
 
-    fn finish(&self, output_ident: Ident) -> syn::Block {
-        let mut ctx = Context::new(output_ident);
+    fn finish(&self, parent: &Context) -> syn::Block {
+        let mut ctx = parent.child();
         self.generate(&mut ctx);
         ctx.finish()
     }
 }
 
+impl<T: Build> Build for [T] {
+    fn generate(&self, ctx: &mut Context) {
+        self.iter().for_each(|node| node.generate(ctx));
+    }
+}
+
+impl<T: Build + ?Sized> Build for &T {
+    fn generate(&self, ctx: &mut Context) {
+        T::generate(self, ctx);
+    }
+}
+
 impl Build for Template {
     fn generate(&self, ctx: &mut Context) {
         self.nodes.iter().for_each(|node| node.generate(ctx));
@@ -43,12 +222,24 @@ impl Build for Node {
     fn generate(&self, ctx: &mut Context) {
         match self {
             Self::Element(elem) => elem.generate(ctx),
+            Self::DynamicElement(elem) => elem.generate(ctx),
+            Self::Component(component) => component.generate(ctx),
+            Self::Slot(slot) => {
+                ctx.push(Part::Error(Error::SlotOutsideComponent(slot.name.clone())))
+            }
+            Self::NamedBlock(named) => named.generate(ctx),
+            Self::Fragment(fragment) => fragment.generate(ctx),
+            Self::Super(token) => match &ctx.super_ {
+                Some(super_) => ctx.push(Part::Render(parse_quote!(#super_))),
+                None => ctx.push(Part::Error(Error::SuperOutsideBlock(token.span))),
+            },
             Self::Block(block) => block.generate(ctx),
             Self::Expr(expr) => expr.generate(ctx),
             Self::If(if_) => if_.generate(ctx),
             Self::Match(match_) => match_.generate(ctx),
             Self::ForLoop(for_loop) => for_loop.generate(ctx),
             Self::Let(let_) => let_.generate(ctx),
+            Self::Error(err) => ctx.push(Part::Error(err.clone())),
         }
     }
 }
@@ -61,16 +252,8 @@ impl Build for Element {
 
         ctx.push(Part::Raw(format!("<{}", self.name)));
 
-        match self.attributes() {
-            Ok(attributes) => {
-                for (name, value) in attributes {
-                    if let Some(value) = value {
-                        ctx.parts.push(Part::Attribute { name, value });
-                    } else {
-                        ctx.push(Part::Raw(format!(" {name}")));
-                    }
-                }
-            }
+        match self.validate_attributes() {
+            Ok(()) => generate_attributes(&self.attr_list, ctx),
             Err(err) => ctx.push(Part::Error(err)),
         }
 
@@ -83,6 +266,444 @@ impl Build for Element {
     }
 }
 
+impl Build for DynamicElement {
+    fn generate(&self, ctx: &mut Context) {
+        let tag = Ident::new("__tag", Span::call_site());
+        let name = &self.name;
+
+        // A block, so that nested elements don't shadow `__tag` before the closing tag.
+        let mut elem_ctx = ctx.child();
+        elem_ctx.push(Part::Stmt(parse_quote! {
+            let #tag: ::gen_html::Tag = #name;
+        }));
+        elem_ctx.push(Part::Raw("<".into()));
+        elem_ctx.push(Part::Render(parse_quote!(#tag)));
+
+        match self.validate_attributes() {
+            Ok(()) => generate_attributes(&self.attr_list, &mut elem_ctx),
+            Err(err) => elem_ctx.push(Part::Error(err)),
+        }
+
+        elem_ctx.push(Part::Raw(">".into()));
+
+        // Void elements can't have children, so the body and the closing tag are skipped.
+        let mut body_ctx = elem_ctx.child();
+        if let Some(body) = &self.body {
+            body.generate(&mut body_ctx);
+        }
+        body_ctx.push(Part::Raw("</".into()));
+        body_ctx.push(Part::Render(parse_quote!(#tag)));
+        body_ctx.push(Part::Raw(">".into()));
+        let body = body_ctx.finish();
+
+        elem_ctx.push(Part::Stmt(parse_quote! {
+            if !#tag.is_void() #body
+        }));
+
+        let block = elem_ctx.finish();
+        ctx.push(Part::Stmt(parse_quote!(#block)));
+    }
+}
+
+fn generate_attributes(attr_list: &[Attribute], ctx: &mut Context) {
+    if !attr_list.iter().any(Attribute::contains_spread) {
+        return AttributeList(attr_list).generate(ctx);
+    }
+
+    // Spread attributes are merged with the others at runtime.
+    let attrs = Ident::new("__attrs", Span::call_site());
+    ctx.push(Part::Stmt(parse_quote! {
+        let mut #attrs = ::gen_html::Attributes::new();
+    }));
+
+    let outer = ctx.attributes.replace(attrs.clone());
+    AttributeList(attr_list).generate(ctx);
+    ctx.attributes = outer;
+
+    ctx.push(Part::Render(parse_quote!(#attrs)));
+}
+
+/// The attributes of an element, or of a branch of an `if` or `match`.
+///
+/// All `.` shorthands are merged into a single `class` attribute, placed where the
+/// first one was written. Shorthands in `if` or `match` branches are merged at runtime,
+/// so the attribute is placed after the others.
+struct AttributeList<'a>(&'a [Attribute]);
+
+impl Build for AttributeList<'_> {
+    fn generate(&self, ctx: &mut Context) {
+        if let Some(list) = ctx.classes.clone() {
+            return self.generate_into(&list, ctx);
+        }
+
+        let branch_classes = self
+            .0
+            .iter()
+            .flat_map(Attribute::branches)
+            .flatten()
+            .any(|attr| !attr.class_spans().is_empty());
+        if branch_classes {
+            let list = Ident::new("__classes", Span::call_site());
+            let len: usize = self.0.iter().map(|attr| attr.class_spans().len()).sum();
+            ctx.push(Part::Stmt(parse_quote! {
+                let mut #list = ::gen_html::__private::ClassList::<#len>::new();
+            }));
+
+            ctx.classes = Some(list.clone());
+            self.generate_into(&list, ctx);
+            ctx.classes = None;
+
+            return push_attribute(ctx, "class".into(), Some(parse_quote!(#list.non_empty())));
+        }
+
+        let classes: Vec<_> = self
+            .0
+            .iter()
+            .filter_map(|attr| match attr {
+                Attribute::Class { value, cond } => Some((value, cond.as_ref())),
+                _ => None,
+            })
+            .collect();
+
+        let mut classes = Some(classes);
+        for attr in self.0 {
+            match attr {
+                Attribute::Class { .. } => {
+                    if let Some(classes) = classes.take() {
+                        generate_classes(&classes, ctx);
+                    }
+                }
+                attr => attr.generate(ctx),
+            }
+        }
+    }
+}
+
+impl AttributeList<'_> {
+    /// Generates the attributes, pushing the `.` shorthands into the `ClassList` `list`.
+    fn generate_into(&self, list: &Ident, ctx: &mut Context) {
+        for attr in self.0 {
+            match attr {
+                Attribute::Class { value, cond } => push_class(list, value, cond.as_ref(), ctx),
+                attr => attr.generate(ctx),
+            }
+        }
+    }
+}
+
+fn generate_classes(classes: &[(&Expr, Option<&Expr>)], ctx: &mut Context) {
+    let literals: Option<Vec<_>> = classes
+        .iter()
+        .map(|(value, cond)| match (value, cond) {
+            (
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }),
+                None,
+            ) => Some(lit),
+            _ => None,
+        })
+        .collect();
+
+    // Static classes are merged at compile time.
+    if let Some(literals) = literals {
+        let values: Vec<_> = literals.iter().map(|lit| lit.value()).collect();
+        let mut tokens = Vec::new();
+        for token in values
+            .iter()
+            .flat_map(|value| value.split_ascii_whitespace())
+        {
+            if !tokens.contains(&token) {
+                tokens.push(token);
+            }
+        }
+        let merged = LitStr::new(&tokens.join(" "), literals[0].span());
+        return push_attribute(ctx, "class".into(), Some(parse_quote!(#merged)));
+    }
+
+    if let [(value, None)] = classes {
+        return push_attribute(ctx, "class".into(), Some((*value).clone()));
+    }
+
+    let list = Ident::new("__classes", Span::call_site());
+    let len = classes.len();
+    ctx.push(Part::Stmt(parse_quote! {
+        let mut #list = ::gen_html::__private::ClassList::<#len>::new();
+    }));
+
+    for (value, cond) in classes {
+        push_class(&list, value, *cond, ctx);
+    }
+
+    push_attribute(ctx, "class".into(), Some(parse_quote!(#list.non_empty())));
+}
+
+/// Pushes `value` into the `ClassList` `list` if `cond` holds.
+fn push_class(list: &Ident, value: &Expr, cond: Option<&Expr>, ctx: &mut Context) {
+    let push: Stmt = match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => {
+            let escaped = LitStr::new(&escape(&lit.value()), lit.span());
+            parse_quote!(#list.push_static(#escaped);)
+        }
+        value => {
+            let value = ref_with_same_span(value.clone());
+            parse_quote!(#list.push(#value);)
+        }
+    };
+
+    ctx.push(Part::Stmt(match cond {
+        Some(cond) => parse_quote!(if #cond { #push }),
+        None => push,
+    }));
+}
+
+impl Build for Attribute {
+    fn generate(&self, ctx: &mut Context) {
+        let value = match self {
+            Self::If(if_) => return if_.generate(ctx),
+            Self::Match(match_) => return match_.generate(ctx),
+            Self::Spread { attrs, .. } => {
+                let collected = ctx
+                    .attributes
+                    .clone()
+                    .expect("elements with spreads collect their attributes");
+                return ctx.push(Part::Stmt(parse_quote! {
+                    ::gen_html::Attributes::spread(&mut #collected, #attrs);
+                }));
+            }
+            Self::Class { .. } => unreachable!("classes are generated by `AttributeList`"),
+            Self::Id(value)
+            | Self::KeyValue {
+                value: Some(value), ..
+            } => Some(value.clone()),
+            Self::KeyValue { value: None, .. } => None,
+        };
+        let (_, name) = self.keys().swap_remove(0);
+
+        push_attribute(ctx, name, value);
+    }
+}
+
+/// Writes the attribute `name`, or collects it for elements with spread attributes.
+fn push_attribute(ctx: &mut Context, name: String, value: Option<Expr>) {
+    match (ctx.attributes.clone(), value) {
+        (Some(collected), value) => {
+            let value = match value {
+                Some(value) => ref_with_same_span(value),
+                None => parse_quote!(&true),
+            };
+            ctx.push(Part::Stmt(parse_quote! {
+                ::gen_html::__private::insert_value(
+                    #value,
+                    ::std::borrow::Cow::Borrowed(#name),
+                    &mut #collected,
+                );
+            }));
+        }
+        (None, Some(value)) => ctx.push(Part::Attribute { name, value }),
+        (None, None) => ctx.push(Part::Raw(format!(" {name}"))),
+    }
+}
+
+impl Build for AttributeIf {
+    fn generate(&self, ctx: &mut Context) {
+        let cond = &self.cond;
+        let (then_branch, then_hint) = ctx.measure(|| AttributeList(&self.then_branch).finish(ctx));
+        let (else_branch, else_hint) = ctx.measure(|| {
+            self.else_branch.as_ref().map(|branch| {
+                let branch = AttributeList(branch).finish(ctx);
+                quote!(else #branch)
+            })
+        });
+        ctx.add_size_hint(then_hint.max(else_hint));
+
+        ctx.push(Part::Stmt(parse_quote! {
+            if #cond #then_branch #else_branch
+        }));
+    }
+}
+
+impl Build for AttributeMatch {
+    fn generate(&self, ctx: &mut Context) {
+        let expr = &self.expr;
+        let mut size_hint = 0;
+        let arms: Vec<_> = self
+            .arms
+            .iter()
+            .map(|arm| {
+                let pat = &arm.pat;
+                let guard = arm.guard.as_ref().map(|guard| quote!(if #guard));
+                let (body, arm_hint) = ctx.measure(|| AttributeList(&arm.attrs).finish(ctx));
+                size_hint = size_hint.max(arm_hint);
+                quote!(#pat #guard => #body)
+            })
+            .collect();
+        ctx.add_size_hint(size_hint);
+
+        ctx.push(Part::Stmt(parse_quote! {
+            match #expr { #(#arms)* }
+        }));
+    }
+}
+
+impl Build for Component {
+    fn generate(&self, ctx: &mut Context) {
+        if let Err(err) = self.validate() {
+            ctx.push(Part::Error(err));
+        }
+
+        if let Some(body) = &self.body
+            && body.nodes.iter().any(Node::contains_fragment)
+        {
+            let name = &self.path.segments.last().expect("path is not empty").ident;
+            ctx.push(Part::Error(Error::FragmentNotAllowed(name.clone())));
+        }
+
+        let path = &self.path;
+        let setters = self.props.iter().map(|prop| match prop {
+            Attribute::Id(id) => quote!(.id(#id)),
+            Attribute::Class { value, .. } => quote!(.class(#value)),
+            Attribute::KeyValue { key, value } => {
+                let value = value.clone().unwrap_or_else(|| parse_quote!(true));
+                quote!(.#key(#value))
+            }
+            Attribute::If(_) | Attribute::Match(_) | Attribute::Spread { .. } => quote!(),
+        });
+        let output = &ctx.output;
+        let nodes = self
+            .body
+            .as_ref()
+            .map(|body| &body.nodes[..])
+            .unwrap_or_default();
+
+        // Components without a builder are called like a function, with the children last.
+        if let Some(args) = &self.args {
+            let args = args.iter();
+            let children = self.body.is_some().then(|| {
+                // Slots and blocks are reported by `validate`.
+                let children: Vec<_> = nodes
+                    .iter()
+                    .filter(|node| !matches!(node, Node::Slot(_) | Node::NamedBlock(_)))
+                    .collect();
+                let mut body_ctx = ctx.child();
+                children.generate(&mut body_ctx);
+                let block = body_ctx.finish();
+                quote! {
+                    ::gen_html::__private::output_fn(|#output: &mut dyn ::gen_html::Output| {
+                        #block
+                        ::std::fmt::Result::Ok(())
+                    })
+                }
+            });
+
+            ctx.push(Part::Render(parse_quote! {
+                #path(#(#args,)* #children)
+            }));
+            return;
+        }
+
+        let (named, children): (Vec<_>, Vec<_>) = nodes
+            .iter()
+            .partition(|node| matches!(node, Node::Slot(_) | Node::NamedBlock(_)));
+
+        let named: Vec<_> = named
+            .iter()
+            .map(|node| match node {
+                Node::Slot(slot) => {
+                    let name = &slot.name;
+                    let mut body_ctx = ctx.child();
+                    slot.body.generate(&mut body_ctx);
+                    let block = body_ctx.finish();
+                    quote! {
+                        .#name(&::gen_html::__private::output_fn(|#output: &mut dyn ::gen_html::Output| {
+                            #block
+                            ::std::fmt::Result::Ok(())
+                        }))
+                    }
+                }
+                Node::NamedBlock(named) => {
+                    let name = &named.name;
+                    let super_ = Ident::new("__super", Span::call_site());
+                    let mut body_ctx = ctx.child();
+                    body_ctx.super_ = Some(super_.clone());
+                    named.body.generate(&mut body_ctx);
+                    let block = body_ctx.finish();
+                    quote! {
+                        .#name(&|#super_: &dyn ::gen_html::Render,
+                                 #output: &mut dyn ::gen_html::Output| {
+                            #block
+                            ::std::fmt::Result::Ok(())
+                        })
+                    }
+                }
+                _ => unreachable!(),
+            })
+            .collect();
+
+        // A body consisting only of slots and blocks doesn't pass any children.
+        let children =
+            (self.body.is_some() && (!children.is_empty() || nodes.is_empty())).then(|| {
+                let mut body_ctx = ctx.child();
+                children.generate(&mut body_ctx);
+                let block = body_ctx.finish();
+                quote! {
+                    .children(::gen_html::__private::output_fn(|#output: &mut dyn ::gen_html::Output| {
+                        #block
+                        ::std::fmt::Result::Ok(())
+                    }))
+                }
+            });
+
+        let build = quote_spanned!(path.span() => build);
+
+        ctx.push(Part::Render(parse_quote! {
+            #path::builder() #(#setters)* #(#named)* #children .#build()
+        }));
+    }
+}
+
+impl Build for NamedBlock {
+    fn generate(&self, ctx: &mut Context) {
+        if self.body.nodes.iter().any(Node::contains_fragment) {
+            ctx.push(Part::Error(Error::FragmentNotAllowed(self.name.clone())));
+        }
+
+        let name = &self.name;
+        let output = &ctx.output;
+        let mut body_ctx = ctx.child();
+        body_ctx.super_ = None;
+        self.body.generate(&mut body_ctx);
+        let block = body_ctx.finish();
+
+        ctx.push(Part::Render(parse_quote! {
+            ::gen_html::Block::or(#name, ::gen_html::__private::output_fn(|#output: &mut dyn ::gen_html::Output| {
+                #block
+                ::std::fmt::Result::Ok(())
+            }))
+        }));
+    }
+}
+
+impl Build for Fragment {
+    fn generate(&self, ctx: &mut Context) {
+        let name = self.name.to_string();
+        let body = self.body.finish(ctx);
+
+        ctx.push(Part::Stmt(parse_quote! {
+            {
+                let __outer = __active.get();
+                if __target == ::std::option::Option::Some(#name) {
+                    __active.set(true);
+                }
+                #body
+                __active.set(__outer);
+            }
+        }));
+    }
+}
+
 impl Build for Block {
     fn generate(&self, ctx: &mut Context) {
         self.nodes.iter().for_each(|node| node.generate(ctx));
@@ -97,11 +718,12 @@ impl Build for Expr {
 
 impl Build for If {
     fn generate(&self, ctx: &mut Context) {
-        let then_branch = self.then_branch.finish(ctx.output.clone());
-        let else_branch = self
-            .else_branch
-            .as_ref()
-            .map(|branch| branch.finish(ctx.output.clone()));
+        let mut scope = ctx.child();
+        scope.bind(|bindings| bindings.visit_expr_mut(&mut self.cond.clone()));
+        let (then_branch, then_hint) = ctx.measure(|| self.then_branch.finish(&scope));
+        let (else_branch, else_hint) =
+            ctx.measure(|| self.else_branch.as_ref().map(|branch| branch.finish(ctx)));
+        ctx.add_size_hint(then_hint.max(else_hint));
 
         ctx.push(Part::Stmt(Stmt::Expr(
             ExprIf {
@@ -128,31 +750,42 @@ impl Build for If {
 
 impl Build for Match {
     fn generate(&self, ctx: &mut Context) {
+        let mut size_hint = 0;
+        let arms = self
+            .arms
+            .iter()
+            .map(|arm| {
+                let mut scope = ctx.child();
+                scope.bind(|bindings| bindings.visit_pat_mut(&mut arm.pat.clone()));
+                let (block, arm_hint) = ctx.measure(|| arm.body.finish(&scope));
+                size_hint = size_hint.max(arm_hint);
+
+                syn::Arm {
+                    attrs: Vec::new(),
+                    pat: arm.pat.clone(),
+                    guard: arm
+                        .guard
+                        .clone()
+                        .map(|guard| (Default::default(), Box::new(guard))),
+                    body: Box::new(Expr::Block(ExprBlock {
+                        attrs: Vec::new(),
+                        label: None,
+                        block,
+                    })),
+                    fat_arrow_token: Default::default(),
+                    comma: Some(Default::default()),
+                }
+            })
+            .collect();
+        ctx.add_size_hint(size_hint);
+
         ctx.push(Part::Stmt(Stmt::Expr(
             ExprMatch {
                 attrs: Vec::new(),
                 match_token: Default::default(),
                 expr: Box::new(self.expr.clone()),
                 brace_token: Brace::default(),
-                arms: self
-                    .arms
-                    .iter()
-                    .map(|arm| syn::Arm {
-                        attrs: Vec::new(),
-                        pat: arm.pat.clone(),
-                        guard: arm
-                            .guard
-                            .clone()
-                            .map(|guard| (Default::default(), Box::new(guard))),
-                        body: Box::new(Expr::Block(ExprBlock {
-                            attrs: Vec::new(),
-                            label: None,
-                            block: arm.body.finish(ctx.output.clone()),
-                        })),
-                        fat_arrow_token: Default::default(),
-                        comma: Some(Default::default()),
-                    })
-                    .collect(),
+                arms,
             }
             .into(),
             Some(<Token![;]>::default()),
@@ -164,7 +797,24 @@ impl Build for ForLoop {
     fn generate(&self, ctx: &mut Context) {
         let pat = Box::new(self.pat.clone());
         let expr = Box::new(self.expr.clone());
-        let body = self.body.finish(ctx.output.clone());
+        let mut scope = ctx.child();
+        scope.bind(|bindings| bindings.visit_pat_mut(&mut self.pat.clone()));
+        let (body, size_hint) = ctx.measure(|| self.body.finish(&scope));
+        ctx.add_size_hint(size_hint * LOOP_SIZE_HINT);
+
+        // A reusable template can't consume a captured variable, tell the user to
+        // add `once;` instead of letting the borrow checker complain about the closure.
+        // The argument is a single token, rustc drops the notes of `on_unimplemented`
+        // for a `&ident` spanned at the user's code.
+        if let Some(ident) = captured_ident(&self.expr, ctx) {
+            let value = quote_spanned!(ident.span()=> __value);
+            ctx.push(Part::Stmt(parse_quote! {
+                {
+                    let __value = &#ident;
+                    ::gen_html::__private::assert_reusable(#value);
+                }
+            }));
+        }
 
         ctx.push(Part::Stmt(Stmt::Expr(
             Expr::ForLoop(ExprForLoop {
@@ -194,28 +844,124 @@ impl Build for Let {
             }),
             semi_token: <Token![;]>::default(),
         })));
+        ctx.bind(|bindings| bindings.visit_pat_mut(&mut self.pat.clone()));
+    }
+}
+
+/// Returns the variable a `for` loop iterates over, if it's captured by the template.
+///
+/// Uppercase names are skipped, they are most likely constants.
+fn captured_ident<'a>(expr: &'a Expr, ctx: &Context) -> Option<&'a Ident> {
+    let Expr::Path(path) = expr else {
+        return None;
+    };
+    let ident = path.path.get_ident()?;
+    let uppercase = ident.to_string().starts_with(char::is_uppercase);
+    (ctx.reusable && !uppercase && !ctx.bindings.contains(ident)).then_some(ident)
+}
+
+/// Collects the variables bound by patterns.
+struct Bindings<'a>(&'a mut Vec<Ident>);
+
+impl VisitMut for Bindings<'_> {
+    fn visit_pat_ident_mut(&mut self, pat: &mut PatIdent) {
+        self.0.push(pat.ident.clone());
+        visit_mut::visit_pat_ident_mut(self, pat);
     }
 }
 
 struct Context {
     output: Ident,
+    /// The parent's content inside of a block override, see [`NamedBlock`].
+    super_: Option<Ident>,
+    /// Whether the template contains fragments, see [`Fragment`].
+    fragments: bool,
+    /// Attributes are collected into this `Attributes` instead of being written,
+    /// used by elements with spread attributes.
+    attributes: Option<Ident>,
+    /// `.` shorthands are pushed into this `ClassList` instead of being written, used by
+    /// elements with classes in `if` or `match` branches.
+    classes: Option<Ident>,
+    /// Whether the template can be rendered more than once, so `for` loops must not
+    /// consume captured variables.
+    reusable: bool,
+    /// Variables bound inside of the template, which a `for` loop may consume.
+    bindings: Vec<Ident>,
+    /// Estimated output length of the whole template, shared with child contexts. Only the
+    /// longest branch of an `if` or `match` is counted, see [`Context::measure`].
+    size_hint: Rc<Cell<usize>>,
     parts: Vec<Part>,
 }
 
+/// Estimated output length of an expression or attribute, in bytes.
+const DYNAMIC_SIZE_HINT: usize = 16;
+
+/// Estimated number of iterations of a `for` loop.
+const LOOP_SIZE_HINT: usize = 4;
+
 impl Context {
     fn new(output: Ident) -> Self {
         Self {
             output,
+            super_: None,
+            fragments: false,
+            attributes: None,
+            classes: None,
+            reusable: false,
+            bindings: Vec::new(),
+            size_hint: Rc::default(),
             parts: Vec::new(),
         }
     }
 
+    fn child(&self) -> Self {
+        Self {
+            output: self.output.clone(),
+            super_: self.super_.clone(),
+            fragments: self.fragments,
+            attributes: self.attributes.clone(),
+            classes: self.classes.clone(),
+            reusable: self.reusable,
+            bindings: self.bindings.clone(),
+            size_hint: self.size_hint.clone(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Runs `f` and returns the estimated length of the output it generated, which is
+    /// not added to the template. Used for branches, which aren't all rendered.
+    fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, usize) {
+        let before = self.size_hint.get();
+        let value = f();
+        let size_hint = self.size_hint.replace(before) - before;
+        (value, size_hint)
+    }
+
+    fn add_size_hint(&self, size_hint: usize) {
+        self.size_hint.set(self.size_hint.get() + size_hint);
+    }
+
+    /// Adds the variables bound by the patterns visited in `visit` to the scope.
+    fn bind(&mut self, visit: impl FnOnce(&mut Bindings)) {
+        visit(&mut Bindings(&mut self.bindings));
+    }
+
     fn finish(self) -> syn::Block {
         let output = self.output;
         let stmts = self
             .parts
             .into_iter()
-            .map(|part| part.into_stmt(&output))
+            .map(|part| match part {
+                Part::Raw(_) | Part::Render(_) | Part::Attribute { .. } if self.fragments => {
+                    let stmt = part.into_stmt(&output);
+                    parse_quote! {
+                        if __active.get() {
+                            #stmt
+                        }
+                    }
+                }
+                part => part.into_stmt(&output),
+            })
             .collect();
 
         syn::Block {
@@ -224,7 +970,43 @@ impl Context {
         }
     }
 
+    /// Returns the output if the template has no dynamic content.
+    fn as_static(&self) -> Option<String> {
+        self.parts
+            .iter()
+            .try_fold(String::new(), |mut html, part| match part {
+                Part::Raw(raw) => {
+                    html.push_str(raw);
+                    Some(html)
+                }
+                _ => None,
+            })
+    }
+
+    /// Adds `part` to the output.
+    ///
+    /// String literals are escaped at compile time and merged with the surrounding
+    /// [`Part::Raw`], so that static content is written with a single call.
     fn push(&mut self, part: Part) {
+        let part = match part {
+            Part::Render(expr) => match str_literal(&expr) {
+                Some(text) => Part::Raw(escape(&text)),
+                None => Part::Render(expr),
+            },
+            Part::Attribute { name, value } => match str_literal(&value) {
+                Some(value) => Part::Raw(format!(" {name}=\"{}\"", escape(&value))),
+                None => Part::Attribute { name, value },
+            },
+            part => part,
+        };
+
+        let size_hint = match &part {
+            Part::Raw(raw) => raw.len(),
+            Part::Render(_) | Part::Attribute { .. } => DYNAMIC_SIZE_HINT,
+            Part::Stmt(_) | Part::Error(_) => 0,
+        };
+        self.size_hint.set(self.size_hint.get() + size_hint);
+
         match (part, self.parts.last_mut()) {
             (Part::Raw(raw), Some(Part::Raw(last))) => last.push_str(&raw),
             (part, _) => self.parts.push(part),
@@ -243,26 +1025,28 @@ enum Part {
 }
 
 impl Part {
+    /// Parts are written to an `Output`, the `&mut String` of an async template coerces
+    /// to one.
     fn into_stmt(self, output: &Ident) -> Stmt {
         match self {
             Self::Raw(lit) => {
-                parse_quote! { ::std::fmt::Display::fmt(#lit, #output)?; }
+                parse_quote! { ::gen_html::Output::write_str(#output, #lit) ?; }
             }
             Self::Render(expr) => {
                 let expr = ref_with_same_span(expr);
 
                 parse_quote! {
-                    ::gen_html::Render::render_to(#expr, #output)?;
+                    ::gen_html::Render::render_to_output(#expr, #output) ?;
                 }
             }
             Self::Attribute { name, value } => {
                 let value = ref_with_same_span(value);
 
                 parse_quote! {
-                    ::gen_html::Value::render_value_to(#value, #name, #output)?;
+                    ::gen_html::__private::render_value_to(#value, #name, #output) ?;
                 }
             }
-            Self::Error(err) => parse_quote! { #err },
+            Self::Error(err) => parse_quote! { { #err } },
             Self::Stmt(stmt) => stmt,
         }
     }
@@ -276,3 +1060,80 @@ fn ref_with_same_span(expr: Expr) -> Expr {
         expr: Box::new(expr),
     })
 }
+
+fn str_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Some(lit.value()),
+        _ => None,
+    }
+}
+
+/// Escapes text the same way as `gen_html::Escaped`.
+///
+/// `gen-html` can't be used from here, a test in its `escape` module checks that both
+/// produce the same output.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::Template;
+
+    #[test]
+    fn folds_string_literals() {
+        let template: Template =
+            syn::parse_str(r#"div ."a" title: "<x>" { "a & b" (name) "!" }"#).unwrap();
+        let expanded = template.expand().to_string();
+
+        assert!(
+            expanded
+                .contains(r#"write_str (__f , "<div class=\"a\" title=\"&lt;x&gt;\">a &amp; b")"#)
+        );
+        assert!(expanded.contains(r#"write_str (__f , "!</div>")"#));
+    }
+
+    #[test]
+    fn checks_captured_loop_variables() {
+        let source = "for a in items { (a) } let local = [1]; for b in local { (b) }";
+        let template: Template = syn::parse_str(source).unwrap();
+        let expanded = template.expand().to_string();
+        assert_eq!(expanded.matches("assert_reusable").count(), 1);
+        assert!(expanded.contains("let __value = & items"));
+
+        let template: Template = syn::parse_str(&format!("once; {source}")).unwrap();
+        assert!(!template.expand().to_string().contains("assert_reusable"));
+    }
+
+    #[test]
+    fn size_hint_of_branches_and_loops() {
+        let template: Template = syn::parse_str(
+            r#"if a { "abcd" } else { "ab" } match b { 1 => { "xyz" } _ => {} } for x in y { "xy" }"#,
+        )
+        .unwrap();
+        let expanded = template.expand().to_string();
+
+        assert!(expanded.ends_with("with_size_hint (15usize)"), "{expanded}");
+    }
+
+    #[test]
+    fn static_template_is_render_fn() {
+        let template: Template = syn::parse_str(r#"p { "static" }"#).unwrap();
+        let expanded = template.expand().to_string();
+
+        assert!(expanded.starts_with(":: gen_html :: __private :: output_fn"));
+        assert!(expanded.contains(r#"write_str (__f , "<p>static</p>")"#));
+    }
+}
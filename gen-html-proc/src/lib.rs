@@ -1,9 +1,12 @@
+use proc_macro2::Span;
 use syn::parse_macro_input;
 
 mod ast;
+mod component;
 mod error;
 mod generate;
 mod parse;
+mod tag_enum;
 mod tags;
 
 #[proc_macro]
@@ -11,3 +14,45 @@ pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let template = parse_macro_input!(input as ast::Template);
     template.expand().into()
 }
+
+#[proc_macro]
+pub fn html_static(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let template = parse_macro_input!(input as ast::Template);
+    template.expand_static().into()
+}
+
+#[proc_macro]
+pub fn try_html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let template = parse_macro_input!(input as ast::Template);
+    template.expand_try().into()
+}
+
+#[proc_macro]
+pub fn html_async(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let template = parse_macro_input!(input as ast::Template);
+    template.expand_async().into()
+}
+
+#[proc_macro_attribute]
+pub fn component(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item = parse_macro_input!(item as syn::ItemFn);
+    if !attr.is_empty() {
+        let err = syn::Error::new(Span::call_site(), "`#[component]` takes no arguments");
+        return err.into_compile_error().into();
+    }
+
+    component::expand(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates the `Tag` enum of `gen-html` from the list of known elements.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __tag_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as tag_enum::TagEnum);
+    tag_enum::expand(input).into()
+}
@@ -1,19 +1,102 @@
-use crate::ast::{Arm, Attribute, Block, Element, ForLoop, If, Let, Match, Node, Template};
+use crate::{
+    ast::{
+        Arm, Attribute, AttributeArm, AttributeIf, AttributeMatch, Block, Capture, Component,
+        DynamicElement, Element, ForLoop, Fragment, If, Let, Match, NamedBlock, Node, Slot,
+        Template,
+    },
+    error::Error,
+};
+use proc_macro2::TokenTree;
 use syn::{
-    Expr, ExprLit, Ident, Lit, LitStr, Pat, PatType, Token, Type, braced, parenthesized,
-    parse::{self, Parse},
+    Expr, ExprLit, Ident, Lit, LitStr, Pat, PatType, Path, Token, Type, braced, bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::{self, Parse, discouraged::Speculative},
+    punctuated::Punctuated,
     token,
 };
 
+mod kw {
+    syn::custom_keyword!(slot);
+    syn::custom_keyword!(block);
+    syn::custom_keyword!(fragment);
+    syn::custom_keyword!(tag);
+    syn::custom_keyword!(once);
+}
+
 impl Parse for Template {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let mut nodes = Vec::new();
+        let capture = if input.peek(Token![ref]) && input.peek2(Token![;]) {
+            let ref_token: Token![ref] = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Capture::Ref(ref_token.span)
+        } else if input.peek(kw::once) && input.peek2(Token![;]) {
+            let once: kw::once = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Capture::Once(once.span)
+        } else {
+            Capture::Move
+        };
+
+        let nodes = parse_nodes(input);
+        Ok(Self { capture, nodes })
+    }
+}
+
+/// Parses nodes until the end of `input`.
+///
+/// A node that fails to parse is replaced with [`Node::Error`] and skipped,
+/// so that the remaining nodes can still be parsed and expanded.
+fn parse_nodes(input: parse::ParseStream) -> Vec<Node> {
+    let mut nodes = Vec::new();
+
+    while !input.is_empty() {
+        let fork = input.fork();
+        match Node::parse(&fork) {
+            Ok(node) => {
+                input.advance_to(&fork);
+                nodes.push(node);
+            }
+            Err(err) => {
+                skip_node(input);
+                nodes.push(Node::Error(Error::Syn(err)));
+            }
+        }
+    }
+
+    nodes
+}
+
+/// Skips tokens up to the end of the node at the start of `input`.
+///
+/// The node ends after a lone `(expr)` or string literal, or after the first
+/// `{ ... }` (including any `else` branches) or `;`. A `let` binding always
+/// ends at its `;`, as its expression may contain blocks.
+fn skip_node(input: parse::ParseStream) {
+    let first = input.step(|cursor| {
+        let (tt, rest) = cursor.token_tree().expect("input is not empty");
+        Ok((tt, rest))
+    });
+
+    let is_let = match first {
+        Ok(TokenTree::Group(_) | TokenTree::Literal(_)) => return,
+        Ok(TokenTree::Punct(punct)) if punct.as_char() == ';' => return,
+        Ok(TokenTree::Ident(ident)) => ident == "let",
+        _ => false,
+    };
 
-        while !input.is_empty() {
-            nodes.push(Node::parse(input)?);
+    while !input.is_empty() {
+        if input.peek(Token![;]) {
+            _ = <Token![;]>::parse(input);
+            return;
         }
 
-        Ok(Self { nodes })
+        let is_brace = input.peek(token::Brace);
+        _ = input.parse::<TokenTree>();
+
+        if is_brace && !is_let && !input.peek(Token![else]) {
+            return;
+        }
     }
 }
 
@@ -38,19 +121,103 @@ impl Parse for Node {
                 attrs: Vec::new(),
                 lit: Lit::Str(s),
             })))
+        } else if input.peek(kw::slot) && input.peek2(Ident) && input.peek3(token::Brace) {
+            Slot::parse(input).map(Self::Slot)
+        } else if input.peek(kw::block) && input.peek2(Ident) && input.peek3(token::Brace) {
+            NamedBlock::parse(input).map(Self::NamedBlock)
+        } else if input.peek(kw::fragment) && input.peek2(Ident) && input.peek3(token::Brace) {
+            Fragment::parse(input).map(Self::Fragment)
+        } else if input.peek(kw::tag) && input.peek2(token::Paren) {
+            DynamicElement::parse(input).map(Self::DynamicElement)
+        } else if input.peek(Token![super]) && !input.peek2(Token![::]) {
+            input.parse().map(Self::Super)
+        } else if is_component(input) {
+            Component::parse(input).map(Self::Component)
         } else {
             Element::parse(input).map(Self::Element)
         }
     }
 }
 
+/// Components are named with a path (`ui::card`) or an uppercase identifier (`Card`).
+fn is_component(input: parse::ParseStream) -> bool {
+    let fork = input.fork();
+    match Ident::parse_any(&fork) {
+        Ok(ident) => {
+            fork.peek(Token![::]) || ident.to_string().starts_with(|c: char| c.is_uppercase())
+        }
+        Err(_) => input.peek(Token![::]),
+    }
+}
+
 impl Parse for Element {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let name = Ident::parse(input)?;
-        let mut attr_list = Vec::new();
+        let (attr_list, body) = parse_element_rest(input, &name)?;
 
-        while input.peek(Ident) || input.peek(Token![@]) || input.peek(Token![.]) {
-            attr_list.push(Attribute::parse(input)?);
+        Ok(Self {
+            name,
+            attr_list,
+            body,
+        })
+    }
+}
+
+impl Parse for DynamicElement {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let tag = input.parse::<kw::tag>()?;
+        let content;
+        parenthesized!(content in input);
+        let name = Expr::parse(&content)?;
+        let (attr_list, body) = parse_element_rest(input, &Ident::new("tag", tag.span))?;
+
+        Ok(Self {
+            name,
+            attr_list,
+            body,
+        })
+    }
+}
+
+/// Parses the attributes and the body, or `;`, of an element called `name`.
+fn parse_element_rest(
+    input: parse::ParseStream,
+    name: &Ident,
+) -> syn::Result<(Vec<Attribute>, Option<Block>)> {
+    let mut attr_list = Vec::new();
+
+    while Attribute::peek(input) {
+        attr_list.push(Attribute::parse(input)?);
+    }
+
+    let body = if input.peek(Token![;]) {
+        <Token![;]>::parse(input)?;
+        None
+    } else if input.peek(token::Brace) {
+        Some(Block::parse(input)?)
+    } else {
+        Err(input.error(format!(
+            "expected an attribute, `{{ ... }}` or `;` after `{name}`"
+        )))?
+    };
+
+    Ok((attr_list, body))
+}
+
+impl Parse for Component {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let path = Path::parse_mod_style(input)?;
+        let args = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Some(Punctuated::parse_terminated(&content)?)
+        } else {
+            None
+        };
+        let mut props = Vec::new();
+
+        while Attribute::peek(input) {
+            props.push(Attribute::parse(input)?);
         }
 
         let body = if input.peek(Token![;]) {
@@ -59,25 +226,85 @@ impl Parse for Element {
         } else if input.peek(token::Brace) {
             Some(Block::parse(input)?)
         } else {
-            Err(input.error("expected curly braces or SEMICOLON"))?
+            let name = &path.segments.last().expect("path is not empty").ident;
+            Err(input.error(format!(
+                "expected a prop, `{{ ... }}` or `;` after `{name}`"
+            )))?
         };
 
         Ok(Self {
-            name,
-            attr_list,
+            path,
+            args,
+            props,
             body,
         })
     }
 }
 
+impl Parse for Slot {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::slot>()?;
+        let name = Ident::parse(input)?;
+        let body = Block::parse(input)?;
+
+        Ok(Self { name, body })
+    }
+}
+
+impl Parse for NamedBlock {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::block>()?;
+        let name = Ident::parse(input)?;
+        let body = Block::parse(input)?;
+
+        Ok(Self { name, body })
+    }
+}
+
+impl Parse for Fragment {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<kw::fragment>()?;
+        let name = Ident::parse(input)?;
+        let body = Block::parse(input)?;
+
+        Ok(Self { name, body })
+    }
+}
+
+impl Attribute {
+    fn peek(input: parse::ParseStream) -> bool {
+        input.peek(Ident)
+            || input.peek(Token![@])
+            || input.peek(Token![.])
+            || input.peek(Token![if])
+            || input.peek(Token![match])
+            || input.peek(Token![..])
+    }
+}
+
 impl Parse for Attribute {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if <Token![@]>::parse(input).is_ok() {
+        if input.peek(Token![if]) {
+            AttributeIf::parse(input).map(Self::If)
+        } else if input.peek(Token![match]) {
+            AttributeMatch::parse(input).map(Self::Match)
+        } else if input.peek(Token![..]) {
+            let dot2_token = input.parse()?;
+            let attrs = parse_spread(input)?;
+            Ok(Self::Spread { dot2_token, attrs })
+        } else if <Token![@]>::parse(input).is_ok() {
             let id = parse_attribute_value(input)?;
             Ok(Self::Id(id))
         } else if <Token![.]>::parse(input).is_ok() {
-            let class = parse_attribute_value(input)?;
-            Ok(Self::Class(class))
+            let value = parse_attribute_value(input)?;
+            let cond = if input.peek(token::Bracket) {
+                let content;
+                bracketed!(content in input);
+                Some(content.parse()?)
+            } else {
+                None
+            };
+            Ok(Self::Class { value, cond })
         } else if let Ok(key) = Ident::parse(input) {
             let value = if <Token![:]>::parse(input).is_ok() {
                 Some(parse_attribute_value(input)?)
@@ -86,22 +313,95 @@ impl Parse for Attribute {
             };
             Ok(Self::KeyValue { key, value })
         } else {
-            Err(syn::Error::new(input.span(), "todo"))
+            Err(input.error("expected an attribute name, `@`, `.`, `..`, `if` or `match`"))
         }
     }
 }
 
-impl Parse for Block {
+impl Parse for AttributeIf {
     fn parse(input: parse::ParseStream) -> syn::Result<Self> {
+        let if_token = input.parse()?;
+        let cond = Expr::parse_without_eager_brace(input)?;
+        let then_branch = parse_attribute_list(input)?;
+        let else_branch = if <Token![else]>::parse(input).is_ok() {
+            if input.peek(Token![if]) {
+                Some(vec![Attribute::If(Self::parse(input)?)])
+            } else {
+                Some(parse_attribute_list(input)?)
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            if_token,
+            cond,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+impl Parse for AttributeMatch {
+    fn parse(input: parse::ParseStream) -> syn::Result<Self> {
+        let match_token = input.parse()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
         let content;
         braced!(content in input);
 
-        let mut nodes = Vec::new();
-
+        let mut arms = Vec::new();
         while !content.is_empty() {
-            nodes.push(Node::parse(&content)?);
+            let pat = Pat::parse_multi_with_leading_vert(&content)?;
+            let guard = content
+                .parse::<Token![if]>()
+                .map(|_| Expr::parse(&content))
+                .ok()
+                .transpose()?;
+            <Token![=>]>::parse(&content)?;
+            let attrs = parse_attribute_list(&content)?;
+            _ = <Token![,]>::parse(&content);
+
+            arms.push(AttributeArm { pat, guard, attrs });
         }
 
+        Ok(Self {
+            match_token,
+            expr,
+            arms,
+        })
+    }
+}
+
+/// Parses the `(attrs)` of `..(attrs)`.
+fn parse_spread(input: parse::ParseStream) -> syn::Result<Expr> {
+    if !input.peek(token::Paren) {
+        return Err(input.error("expected `(`, spread attributes are written as `..(attrs)`"));
+    }
+
+    let content;
+    parenthesized!(content in input);
+    content.parse()
+}
+
+/// Parses `{ attributes }`.
+fn parse_attribute_list(input: parse::ParseStream) -> syn::Result<Vec<Attribute>> {
+    let content;
+    braced!(content in input);
+
+    let mut attrs = Vec::new();
+    while !content.is_empty() {
+        attrs.push(Attribute::parse(&content)?);
+    }
+
+    Ok(attrs)
+}
+
+impl Parse for Block {
+    fn parse(input: parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        braced!(content in input);
+
+        let nodes = parse_nodes(&content);
         Ok(Self { nodes })
     }
 }
@@ -206,16 +506,87 @@ impl Parse for Let {
 }
 
 fn parse_attribute_value(input: syn::parse::ParseStream) -> syn::Result<Expr> {
-    <LitStr as Parse>::parse(input)
-        .map(|s| {
-            Expr::Lit(ExprLit {
-                attrs: Vec::new(),
-                lit: Lit::Str(s),
-            })
-        })
-        .or_else(|_| {
-            let content;
-            parenthesized!(content in input);
-            Expr::parse(&content)
-        })
+    if input.peek(LitStr) {
+        let s = <LitStr as Parse>::parse(input)?;
+        Ok(Expr::Lit(ExprLit {
+            attrs: Vec::new(),
+            lit: Lit::Str(s),
+        }))
+    } else if input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in input);
+        Expr::parse(&content)
+    } else {
+        Err(input.error(
+            "expected a string literal or a parenthesized expression as the attribute value",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{Node, Template};
+
+    #[test]
+    fn recovers_after_invalid_node() {
+        let template: Template =
+            syn::parse_str(r#"p { (1 +) "text" } img src: 12; if a + { } else { } span { }"#)
+                .unwrap();
+
+        let [
+            Node::Element(p),
+            Node::Error(_),
+            Node::Error(_),
+            Node::Element(span),
+        ] = &template.nodes[..]
+        else {
+            panic!("unexpected nodes");
+        };
+
+        let nodes = &p.body.as_ref().unwrap().nodes;
+        assert!(matches!(nodes[..], [Node::Error(_), Node::Expr(_)]));
+        assert_eq!(span.name, "span");
+    }
+
+    #[test]
+    fn recovers_after_invalid_let() {
+        let template: Template = syn::parse_str(
+            r#"let x = if c { 1 } else { + }; p { } let y = match z { _ => { 2 } } + ; (x)"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            template.nodes[..],
+            [
+                Node::Error(_),
+                Node::Element(_),
+                Node::Error(_),
+                Node::Expr(_)
+            ]
+        ));
+    }
+
+    #[test]
+    fn component_forms() {
+        let template: Template =
+            syn::parse_str(r#"Card title: "a" { } ui::panel("b", 1) { } Badge(3); super::panel("x") { "y" } super"#)
+                .unwrap();
+
+        let [
+            Node::Component(card),
+            Node::Component(panel),
+            Node::Component(badge),
+            Node::Component(super_panel),
+            Node::Super(_),
+        ] = &template.nodes[..]
+        else {
+            panic!("unexpected nodes");
+        };
+
+        assert!(card.args.is_none() && card.props.len() == 1);
+        assert_eq!(panel.args.as_ref().unwrap().len(), 2);
+        assert!(panel.props.is_empty() && panel.body.is_some());
+        assert!(badge.args.is_some() && badge.body.is_none());
+        assert!(super_panel.args.is_some() && super_panel.body.is_some());
+    }
 }
@@ -0,0 +1,73 @@
+use crate::tags;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Ident, Token, Visibility, parse::Parse};
+
+/// The enum declaration without variants, `#[attrs] pub enum Tag;`.
+pub struct TagEnum {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+}
+
+impl Parse for TagEnum {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![enum]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(Self { attrs, vis, ident })
+    }
+}
+
+/// Expands to an enum with a variant for every known element.
+pub fn expand(input: TagEnum) -> TokenStream {
+    let TagEnum { attrs, vis, ident } = input;
+    let names = tags::elements();
+    let variants: Vec<_> = names
+        .iter()
+        .map(|name| {
+            let mut chars = name.chars();
+            let first = chars.next().expect("tag names are not empty");
+            let variant = first.to_uppercase().chain(chars).collect::<String>();
+            Ident::new(&variant, Span::call_site())
+        })
+        .collect();
+    let docs = names.iter().map(|name| format!("`<{name}>`"));
+    let void = names
+        .iter()
+        .zip(&variants)
+        .filter(|(name, _)| tags::is_self_closing(name) == Some(true))
+        .map(|(_, variant)| variant);
+
+    quote! {
+        #(#attrs)*
+        #vis enum #ident {
+            #(#[doc = #docs] #variants,)*
+        }
+
+        impl #ident {
+            /// Returns the name of this element, eg. `"div"`.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+
+            /// Returns `true` for void elements, which have no children and no closing tag.
+            pub const fn is_void(self) -> bool {
+                matches!(self, #(Self::#void)|*)
+            }
+
+            /// Returns the element called `name`, if there is one.
+            pub fn from_name(name: &str) -> ::std::option::Option<Self> {
+                match name {
+                    #(#names => ::std::option::Option::Some(Self::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,68 @@
+/// Names of all known elements.
+pub fn elements() -> &'static [&'static str] {
+    &ELEMENTS
+}
+
 pub fn is_self_closing(name: &str) -> Option<bool> {
     ELEMENTS.contains(&name).then_some(VOID.contains(&name))
 }
 
+/// Returns `true` if `attribute` may be specified on the `tag` element.
+///
+/// Names containing a `-`, like `data-*`, `aria-*` or the attributes of libraries such as
+/// htmx (`hx-get`) and Alpine.js (`x-on:click`), are allowed on every element.
+pub fn is_valid_attribute(tag: &str, attribute: &str) -> bool {
+    attribute.contains('-')
+        || GLOBAL_ATTRIBUTES.contains(&attribute)
+        || element_attributes(tag).contains(&attribute)
+}
+
+/// Returns the valid tag name closest to `name`, if there is one that is close enough.
+pub fn suggest_tag(name: &str) -> Option<&'static str> {
+    closest(name, ELEMENTS.iter().copied())
+}
+
+/// Returns the attribute of `tag` closest to `attribute`, if there is one that is close enough.
+pub fn suggest_attribute(tag: &str, attribute: &str) -> Option<&'static str> {
+    let candidates = element_attributes(tag).iter().chain(GLOBAL_ATTRIBUTES);
+    closest(attribute, candidates.copied())
+}
+
+fn closest(name: &str, candidates: impl Iterator<Item = &'static str>) -> Option<&'static str> {
+    let max_distance = (name.len() / 3).max(1);
+
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Edit distance between `a` and `b`, counting swapped adjacent characters as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    (0..=a.len()).for_each(|i| d[i][0] = i);
+    (0..=b.len()).for_each(|j| d[0][j] = j);
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
 macro_rules! void_elements {
     ($($tag:ident)*) => {
         const VOID: [&str; 13] = [$(stringify!($tag),)*];
@@ -142,3 +203,293 @@ html_elements! {
     video
     wbr
 }
+
+macro_rules! global_attributes {
+    ($($attr:literal)*) => {
+        const GLOBAL_ATTRIBUTES: &[&str] = &[$($attr,)*];
+    };
+}
+
+global_attributes! {
+    "accesskey"
+    "autocapitalize"
+    "autocorrect"
+    "autofocus"
+    "class"
+    "contenteditable"
+    "dir"
+    "draggable"
+    "enterkeyhint"
+    "exportparts"
+    "hidden"
+    "id"
+    "inert"
+    "inputmode"
+    "is"
+    "itemid"
+    "itemprop"
+    "itemref"
+    "itemscope"
+    "itemtype"
+    "lang"
+    "nonce"
+    "part"
+    "popover"
+    "role"
+    "slot"
+    "spellcheck"
+    "style"
+    "tabindex"
+    "title"
+    "translate"
+    "virtualkeyboardpolicy"
+    "writingsuggestions"
+
+    "onabort"
+    "onanimationcancel"
+    "onanimationend"
+    "onanimationiteration"
+    "onanimationstart"
+    "onauxclick"
+    "onbeforeinput"
+    "onbeforematch"
+    "onbeforetoggle"
+    "onblur"
+    "oncancel"
+    "oncanplay"
+    "oncanplaythrough"
+    "onchange"
+    "onclick"
+    "onclose"
+    "oncontextlost"
+    "oncontextmenu"
+    "oncontextrestored"
+    "oncopy"
+    "oncuechange"
+    "oncut"
+    "ondblclick"
+    "ondrag"
+    "ondragend"
+    "ondragenter"
+    "ondragleave"
+    "ondragover"
+    "ondragstart"
+    "ondrop"
+    "ondurationchange"
+    "onemptied"
+    "onended"
+    "onerror"
+    "onfocus"
+    "onfocusin"
+    "onfocusout"
+    "onformdata"
+    "ongotpointercapture"
+    "oninput"
+    "oninvalid"
+    "onkeydown"
+    "onkeypress"
+    "onkeyup"
+    "onload"
+    "onloadeddata"
+    "onloadedmetadata"
+    "onloadstart"
+    "onlostpointercapture"
+    "onmousedown"
+    "onmouseenter"
+    "onmouseleave"
+    "onmousemove"
+    "onmouseout"
+    "onmouseover"
+    "onmouseup"
+    "onpaste"
+    "onpause"
+    "onplay"
+    "onplaying"
+    "onpointercancel"
+    "onpointerdown"
+    "onpointerenter"
+    "onpointerleave"
+    "onpointermove"
+    "onpointerout"
+    "onpointerover"
+    "onpointerrawupdate"
+    "onpointerup"
+    "onprogress"
+    "onratechange"
+    "onreset"
+    "onresize"
+    "onscroll"
+    "onscrollend"
+    "onsecuritypolicyviolation"
+    "onseeked"
+    "onseeking"
+    "onselect"
+    "onselectionchange"
+    "onselectstart"
+    "onslotchange"
+    "onstalled"
+    "onsubmit"
+    "onsuspend"
+    "ontimeupdate"
+    "ontoggle"
+    "ontouchcancel"
+    "ontouchend"
+    "ontouchmove"
+    "ontouchstart"
+    "ontransitioncancel"
+    "ontransitionend"
+    "ontransitionrun"
+    "ontransitionstart"
+    "onvolumechange"
+    "onwaiting"
+    "onwheel"
+}
+
+macro_rules! element_attributes {
+    ($($tag:ident: [$($attr:literal)*])*) => {
+        fn element_attributes(tag: &str) -> &'static [&'static str] {
+            match tag {
+                $(stringify!($tag) => &[$($attr,)*],)*
+                _ => &[],
+            }
+        }
+    };
+}
+
+element_attributes! {
+    a: ["href" "target" "download" "ping" "rel" "hreflang" "type" "referrerpolicy"]
+    area: ["alt" "coords" "shape" "href" "target" "download" "ping" "rel" "referrerpolicy"]
+    audio: ["src" "crossorigin" "preload" "autoplay" "loop" "muted" "controls"]
+    base: ["href" "target"]
+    blockquote: ["cite"]
+    body: [
+        "onafterprint" "onbeforeprint" "onbeforeunload" "onhashchange" "onlanguagechange"
+        "onmessage" "onmessageerror" "onoffline" "ononline" "onpagehide" "onpagereveal"
+        "onpageshow" "onpageswap" "onpopstate" "onrejectionhandled" "onstorage"
+        "onunhandledrejection" "onunload"
+    ]
+    button: [
+        "command" "commandfor" "disabled" "form" "formaction" "formenctype" "formmethod"
+        "formnovalidate" "formtarget" "name" "popovertarget" "popovertargetaction" "type" "value"
+    ]
+    canvas: ["width" "height"]
+    col: ["span"]
+    colgroup: ["span"]
+    data: ["value"]
+    del: ["cite" "datetime"]
+    details: ["name" "open"]
+    dialog: ["open" "closedby"]
+    embed: ["src" "type" "width" "height"]
+    fieldset: ["disabled" "form" "name"]
+    form: [
+        "accept-charset" "action" "autocomplete" "enctype" "method" "name" "novalidate" "rel"
+        "target"
+    ]
+    iframe: [
+        "src" "srcdoc" "name" "sandbox" "allow" "allowfullscreen" "width" "height"
+        "referrerpolicy" "loading"
+    ]
+    img: [
+        "alt" "src" "srcset" "sizes" "crossorigin" "usemap" "ismap" "width" "height"
+        "referrerpolicy" "decoding" "loading" "fetchpriority"
+    ]
+    input: [
+        "accept" "alpha" "alt" "autocomplete" "checked" "colorspace" "dirname" "disabled" "form"
+        "formaction" "formenctype" "formmethod" "formnovalidate" "formtarget" "height" "list"
+        "max" "maxlength" "min" "minlength" "multiple" "name" "pattern" "placeholder"
+        "popovertarget" "popovertargetaction" "readonly" "required" "size" "src" "step" "type"
+        "value" "width"
+    ]
+    ins: ["cite" "datetime"]
+    label: ["for"]
+    li: ["value"]
+    link: [
+        "href" "crossorigin" "rel" "as" "media" "hreflang" "type" "sizes" "imagesrcset"
+        "imagesizes" "referrerpolicy" "integrity" "blocking" "color" "disabled" "fetchpriority"
+    ]
+    map: ["name"]
+    meta: ["name" "http-equiv" "content" "charset" "media"]
+    meter: ["value" "min" "max" "low" "high" "optimum"]
+    object: ["data" "type" "name" "form" "width" "height"]
+    ol: ["reversed" "start" "type"]
+    optgroup: ["disabled" "label"]
+    option: ["disabled" "label" "selected" "value"]
+    output: ["for" "form" "name"]
+    param: ["name" "value"]
+    progress: ["value" "max"]
+    q: ["cite"]
+    script: [
+        "src" "type" "nomodule" "async" "defer" "blocking" "crossorigin" "referrerpolicy"
+        "integrity" "fetchpriority"
+    ]
+    select: ["autocomplete" "disabled" "form" "multiple" "name" "required" "size"]
+    source: ["type" "media" "src" "srcset" "sizes" "width" "height"]
+    style: ["media" "blocking"]
+    td: ["colspan" "rowspan" "headers"]
+    template: [
+        "shadowrootmode" "shadowrootdelegatesfocus" "shadowrootclonable"
+        "shadowrootserializable"
+    ]
+    textarea: [
+        "autocomplete" "cols" "dirname" "disabled" "form" "maxlength" "minlength" "name"
+        "placeholder" "readonly" "required" "rows" "wrap"
+    ]
+    th: ["colspan" "rowspan" "headers" "scope" "abbr"]
+    time: ["datetime"]
+    track: ["default" "kind" "label" "src" "srclang"]
+    video: [
+        "src" "crossorigin" "poster" "preload" "autoplay" "playsinline" "loop" "muted"
+        "controls" "width" "height"
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, is_valid_attribute, suggest_attribute, suggest_tag};
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("div", "div"), 0);
+        assert_eq!(edit_distance("dvi", "div"), 1);
+        assert_eq!(edit_distance("hreff", "href"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(suggest_tag("dvi"), Some("div"));
+        assert_eq!(suggest_tag("buton"), Some("button"));
+        assert_eq!(suggest_tag("xyzzy"), None);
+
+        assert_eq!(suggest_attribute("a", "hreff"), Some("href"));
+        assert_eq!(suggest_attribute("img", "srcc"), Some("src"));
+        assert_eq!(suggest_attribute("div", "clas"), Some("class"));
+        assert_eq!(suggest_attribute("div", "href"), None);
+    }
+
+    #[test]
+    fn attributes() {
+        assert!(is_valid_attribute("div", "class"));
+        assert!(is_valid_attribute("span", "hidden"));
+        assert!(is_valid_attribute("a", "href"));
+        assert!(is_valid_attribute("img", "src"));
+        assert!(is_valid_attribute("input", "checked"));
+        assert!(is_valid_attribute("p", "data-user-id"));
+        assert!(is_valid_attribute("button", "aria-label"));
+        assert!(is_valid_attribute("div", "hx-get"));
+        assert!(is_valid_attribute("div", "x-on:click"));
+        assert!(is_valid_attribute("div", "part"));
+        assert!(is_valid_attribute("div", "exportparts"));
+        assert!(is_valid_attribute("div", "onpointerdown"));
+        assert!(is_valid_attribute("div", "onanimationend"));
+        assert!(is_valid_attribute("div", "ontransitionend"));
+
+        assert!(!is_valid_attribute("a", "hreff"));
+        assert!(!is_valid_attribute("div", "href"));
+        assert!(!is_valid_attribute("img", "checked"));
+
+        assert!(is_valid_attribute("xyzzy", "id"));
+        assert!(is_valid_attribute("xyzzy", "data-x"));
+        assert!(!is_valid_attribute("xyzzy", "src"));
+    }
+}
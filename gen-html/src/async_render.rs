@@ -0,0 +1,137 @@
+use crate::{Raw, Render};
+use std::fmt;
+
+/// Trait for rendering HTML asynchronously.
+///
+/// `AsyncRender` is implemented for templates created with the [`html_async!`] macro,
+/// and for every type that implements [`Render`].
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{AsyncRender, html_async};
+///
+/// async fn user_name(id: u32) -> String {
+///     format!("user #{id}")
+/// }
+///
+/// async fn page() -> String {
+///     let page = html_async! {
+///         let name = user_name(1).await;
+///         h1 { (name) }
+///         p { "Welcome back, " (user_name(1).await) "!" }
+///     };
+///     page.render_async().await.0
+/// }
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     match std::pin::pin!(future).poll(&mut cx) {
+/// #         std::task::Poll::Ready(output) => output,
+/// #         std::task::Poll::Pending => unreachable!(),
+/// #     }
+/// # }
+/// # let html = block_on(page());
+/// # assert_eq!(html, "<h1>user #1</h1><p>Welcome back, user #1!</p>");
+/// ```
+///
+/// [`html_async!`]: crate::html_async
+pub trait AsyncRender {
+    /// Appends the rendered HTML to `buf`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if rendering fails, like [`Render::render_to`].
+    fn render_into_async(&self, buf: &mut String) -> impl Future<Output = fmt::Result>;
+
+    /// Renders HTML to a `Raw<String>`, returning [`Err`] if rendering fails.
+    fn try_render_async(&self) -> impl Future<Output = Result<Raw<String>, fmt::Error>> {
+        async {
+            let mut buf = String::new();
+            self.render_into_async(&mut buf).await?;
+            Ok(Raw(buf))
+        }
+    }
+
+    /// Renders HTML to a `Raw<String>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if rendering fails, like [`Render::render`].
+    fn render_async(&self) -> impl Future<Output = Raw<String>> {
+        async {
+            self.try_render_async()
+                .await
+                .expect("render_into_async returned Err")
+        }
+    }
+
+    /// Renders HTML to an [`axum`] response, or `500 Internal Server Error` if rendering
+    /// fails.
+    ///
+    /// ```
+    /// use axum::{Router, response::Response, routing::get};
+    /// use gen_html::{AsyncRender, html_async};
+    ///
+    /// async fn greeting() -> &'static str {
+    ///     "Hello"
+    /// }
+    ///
+    /// async fn index() -> Response {
+    ///     html_async! { h1 { (greeting().await) } }.respond().await
+    /// }
+    ///
+    /// let app: Router = Router::new().route("/", get(index));
+    /// ```
+    ///
+    /// [`axum`]: https://docs.rs/axum
+    #[cfg(feature = "axum")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+    fn respond(&self) -> impl Future<Output = axum::response::Response> {
+        async { crate::web::respond_with(self.try_render_async().await) }
+    }
+}
+
+impl<T: Render + ?Sized> AsyncRender for T {
+    async fn render_into_async(&self, buf: &mut String) -> fmt::Result {
+        self.render_into(buf)
+    }
+}
+
+/// Implements [`AsyncRender`] using an async function.
+///
+/// This `struct` is created by [`async_render_fn()`].
+pub struct AsyncRenderFn<F> {
+    f: F,
+    size_hint: usize,
+}
+
+impl<F> AsyncRenderFn<F> {
+    /// Sets the number of bytes reserved before rendering.
+    pub fn with_size_hint(self, size_hint: usize) -> Self {
+        Self { size_hint, ..self }
+    }
+}
+
+impl<F> fmt::Debug for AsyncRenderFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncRenderFn").finish()
+    }
+}
+
+impl<F> AsyncRender for AsyncRenderFn<F>
+where
+    F: AsyncFn(&mut String) -> fmt::Result,
+{
+    async fn render_into_async(&self, buf: &mut String) -> fmt::Result {
+        buf.reserve(self.size_hint);
+        (self.f)(buf).await
+    }
+}
+
+/// Creates a type whose [`AsyncRender`] impl is provided with the async function `f`.
+pub fn async_render_fn<F>(f: F) -> AsyncRenderFn<F>
+where
+    F: AsyncFn(&mut String) -> fmt::Result,
+{
+    AsyncRenderFn { f, size_hint: 0 }
+}
@@ -0,0 +1,274 @@
+use crate::{Output, Render, Value, output::FormatterOutput, value};
+use std::{borrow::Cow, fmt};
+
+/// A dynamic collection of HTML attributes.
+///
+/// `Attributes` can be spread onto an element inside of the [`html!`] macro using
+/// `..(attrs)`. Attributes are merged in the order they are written:
+///
+/// - `class` values are concatenated, separated by a space.
+/// - For every other attribute, the value written last wins.
+///
+/// Attribute names are checked when they are inserted, see [`Attributes::insert`].
+/// Values are escaped.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{Attributes, html};
+///
+/// let mut attrs = Attributes::new();
+/// attrs.insert("class", "primary");
+/// attrs.insert("title", "Save changes");
+/// attrs.insert("hx-post", "/save");
+///
+/// let markup = html! {
+///     button ."btn" title: "Save" ..(&attrs) { "Save" }
+/// };
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     r#"<button class="btn primary" title="Save changes" hx-post="/save">Save</button>"#
+/// # );
+/// ```
+///
+/// Templates can be rendered more than once, so spread a reference to `Attributes` that
+/// are owned by the template. Any iterator of `(name, value)` pairs can be spread as well, where `value` implements
+/// [`Value`].
+///
+/// ```
+/// # use gen_html::html;
+/// let data = [("data-id", "7"), ("data-kind", "user")];
+///
+/// let markup = html! {
+///     div ..(data) {}
+/// };
+/// # assert_eq!(markup.to_string(), r#"<div data-id="7" data-kind="user"></div>"#);
+/// ```
+///
+/// [`html!`]: crate::html
+#[derive(Debug, Clone, Default)]
+pub struct Attributes {
+    /// Names with escaped values, `None` for boolean attributes.
+    entries: Vec<(Cow<'static, str>, Option<String>)>,
+}
+
+impl Attributes {
+    /// Creates an empty collection of attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts an attribute, merging it with an existing attribute of the same name.
+    ///
+    /// `value` is handled the same way as inside of [`html!`], see [`Value`]. A value that
+    /// omits the attribute, like `false` or `None`, inserts nothing, so it doesn't remove an
+    /// attribute inserted earlier. Use [`Attributes::remove`] for that.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `name` is not a valid attribute name, eg. it is empty or
+    /// contains whitespace, `"`, `'`, `<`, `>`, `/`, `=` or `&`. In release builds the
+    /// attribute is ignored.
+    ///
+    /// [`html!`]: crate::html
+    pub fn insert(&mut self, name: impl Into<Cow<'static, str>>, value: impl Value) {
+        value::insert_value(&value, name.into(), self);
+    }
+
+    /// Removes the attribute called `name`.
+    pub fn remove(&mut self, name: &str) {
+        self.entries.retain(|(key, _)| key != name);
+    }
+
+    /// Returns `true` if an attribute called `name` is present.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|(key, _)| key == name)
+    }
+
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[doc(hidden)]
+    pub fn spread(&mut self, attrs: impl Spread) {
+        attrs.spread_into(self);
+    }
+
+    /// Inserts an attribute with an already escaped value.
+    pub(crate) fn insert_escaped(&mut self, name: Cow<'static, str>, value: Option<String>) {
+        if !is_valid_name(&name) {
+            debug_assert!(false, "invalid attribute name: {name:?}");
+            return;
+        }
+
+        let Some(index) = self.entries.iter().position(|(key, _)| *key == name) else {
+            self.entries.push((name, value));
+            return;
+        };
+
+        match (&mut self.entries[index].1, value) {
+            (Some(class), Some(value)) if name == "class" => {
+                if !class.is_empty() && !value.is_empty() {
+                    class.push(' ');
+                }
+                class.push_str(&value);
+            }
+            (_, value) => {
+                // The attribute moves to the end, like it was written there.
+                self.entries.remove(index);
+                self.entries.push((name, value));
+            }
+        }
+    }
+}
+
+impl Render for Attributes {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to_output(&mut FormatterOutput(f))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        for (name, value) in &self.entries {
+            out.write_str(" ")?;
+            out.write_str(name)?;
+            if let Some(value) = value {
+                out.write_str("=\"")?;
+                out.write_str(value)?;
+                out.write_str("\"")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<N, V> FromIterator<(N, V)> for Attributes
+where
+    N: Into<Cow<'static, str>>,
+    V: Value,
+{
+    fn from_iter<I: IntoIterator<Item = (N, V)>>(iter: I) -> Self {
+        let mut attrs = Self::new();
+        attrs.extend(iter);
+        attrs
+    }
+}
+
+impl<N, V> Extend<(N, V)> for Attributes
+where
+    N: Into<Cow<'static, str>>,
+    V: Value,
+{
+    fn extend<I: IntoIterator<Item = (N, V)>>(&mut self, iter: I) {
+        for (name, value) in iter {
+            self.insert(name, value);
+        }
+    }
+}
+
+/// Types that can be spread onto an element using `..(attrs)`.
+///
+/// This is implemented for [`Attributes`] and iterators of `(name, value)` pairs.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be spread as attributes",
+    note = "spread `Attributes` or an iterator of `(name, value)` pairs"
+)]
+pub trait Spread {
+    /// Merges `self` into `attrs`.
+    fn spread_into(self, attrs: &mut Attributes);
+}
+
+impl Spread for Attributes {
+    fn spread_into(self, attrs: &mut Attributes) {
+        for (name, value) in self.entries {
+            attrs.insert_escaped(name, value);
+        }
+    }
+}
+
+impl Spread for &Attributes {
+    fn spread_into(self, attrs: &mut Attributes) {
+        for (name, value) in &self.entries {
+            attrs.insert_escaped(name.clone(), value.clone());
+        }
+    }
+}
+
+impl<I, N, V> Spread for I
+where
+    I: IntoIterator<Item = (N, V)>,
+    N: Into<Cow<'static, str>>,
+    V: Value,
+{
+    fn spread_into(self, attrs: &mut Attributes) {
+        attrs.extend(self);
+    }
+}
+
+/// Checks if `name` is a valid attribute name according to the HTML spec.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|ch| {
+            !ch.is_control()
+                && !ch.is_whitespace()
+                && !matches!(ch, '"' | '\'' | '>' | '/' | '=' | '<' | '&')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Attributes, is_valid_name};
+    use crate::Render;
+
+    #[test]
+    fn merge() {
+        let mut attrs = Attributes::new();
+        attrs.insert("class", "a");
+        attrs.insert("id", "first");
+        attrs.insert("class", "b");
+        attrs.insert("id", "second");
+        attrs.insert("hidden", true);
+        attrs.insert("title", None::<&str>);
+
+        assert_eq!(attrs.render().0, r#" class="a b" id="second" hidden"#);
+    }
+
+    #[test]
+    fn escaping() {
+        let attrs: Attributes = [("data-x", "\"><script>")].into_iter().collect();
+
+        assert_eq!(attrs.render().0, r#" data-x="&quot;&gt;&lt;script&gt;""#);
+    }
+
+    #[test]
+    fn valid_names() {
+        assert!(is_valid_name("data-x"));
+        assert!(is_valid_name("hx-on:click"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("onclick=\"alert(1)\""));
+        assert!(!is_valid_name("a b"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "invalid attribute name"]
+    fn invalid_name() {
+        let mut attrs = Attributes::new();
+        attrs.insert("onclick=\"alert(1)\"", "x");
+    }
+
+    #[test]
+    fn omitted_value_keeps_earlier_value() {
+        let mut attrs = Attributes::new();
+        attrs.insert("hidden", true);
+        attrs.insert("hidden", false);
+
+        assert_eq!(attrs.render().0, " hidden");
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Output, Render, render::output_fn};
+use std::fmt;
+
+type Override<'a> = dyn Fn(&dyn Render, &mut dyn Output) -> fmt::Result + 'a;
+
+/// An overridable block of a layout [component].
+///
+/// Inside of a layout, `block name { ... }` renders the block `name` with the given
+/// default content. A page extends the layout by using it as a component, and overrides
+/// blocks with `block name { ... }` inside of its body. Inside of an override, `super`
+/// renders the layout's default content.
+///
+/// Block parameters are marked with `#[prop(block)]`.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{Block, Render, component, html};
+///
+/// #[component]
+/// fn base(#[prop(block)] title: Block, #[prop(block)] main: Block) -> impl Render {
+///     html! {
+///         title { block title { "My site" } }
+///         main { block main { "Nothing here yet." } }
+///     }
+/// }
+///
+/// let about = html! {
+///     Base {
+///         block title { "About | " super }
+///         block main { p { "About us" } }
+///     }
+/// };
+/// # assert_eq!(
+/// #     about.to_string(),
+/// #     "<title>About | My site</title><main><p>About us</p></main>"
+/// # );
+///
+/// let empty = html! { Base; };
+/// # assert_eq!(
+/// #     empty.to_string(),
+/// #     "<title>My site</title><main>Nothing here yet.</main>"
+/// # );
+/// ```
+///
+/// [component]: crate::component
+#[derive(Clone, Copy, Default)]
+pub struct Block<'a>(Option<&'a Override<'a>>);
+
+impl<'a> Block<'a> {
+    /// Returns `true` if this block was overridden.
+    pub fn is_overridden(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Renders the override of this block, or `parent` if it wasn't overridden.
+    ///
+    /// The override may include `parent` using `super`.
+    pub fn or<R: Render>(self, parent: R) -> impl Render + use<'a, R> {
+        output_fn(move |out| match self.0 {
+            Some(block) => block(&parent, out),
+            None => parent.render_to_output(out),
+        })
+    }
+}
+
+impl fmt::Debug for Block<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Block")
+            .field("overridden", &self.is_overridden())
+            .finish()
+    }
+}
+
+impl<'a, F> From<&'a F> for Block<'a>
+where
+    F: Fn(&dyn Render, &mut dyn Output) -> fmt::Result,
+{
+    fn from(block: &'a F) -> Self {
+        Self(Some(block))
+    }
+}
@@ -0,0 +1,122 @@
+//! Class lists generated by the [`html!`] macro for elements with several `.` shorthands.
+//!
+//! [`html!`]: crate::html
+
+use crate::{Output, Render, Value, output::FormatterOutput, value};
+use std::{borrow::Cow, fmt};
+
+/// Up to `N` class entries, rendered as a single de-duplicated class list.
+///
+/// Static entries are borrowed, only dynamic entries are rendered into a `String`.
+pub struct ClassList<const N: usize> {
+    /// Escaped entries, each may contain several classes separated by whitespace.
+    entries: [Cow<'static, str>; N],
+    len: usize,
+}
+
+impl<const N: usize> ClassList<N> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            entries: [const { Cow::Borrowed("") }; N],
+            len: 0,
+        }
+    }
+
+    /// Adds a class that was escaped by the macro.
+    pub fn push_static(&mut self, class: &'static str) {
+        self.push_entry(Cow::Borrowed(class));
+    }
+
+    /// Adds a dynamic class, see [`Value`].
+    pub fn push(&mut self, class: &impl Value) {
+        if let Some(class) = value::render_value(class) {
+            self.push_entry(Cow::Owned(class));
+        }
+    }
+
+    fn push_entry(&mut self, entry: Cow<'static, str>) {
+        self.entries[self.len] = entry;
+        self.len += 1;
+    }
+
+    /// Returns `None` if there are no classes, so that the attribute is omitted.
+    pub fn non_empty(&self) -> Option<&Self> {
+        self.classes().next().is_some().then_some(self)
+    }
+
+    fn classes(&self) -> impl Iterator<Item = &str> {
+        self.entries[..self.len]
+            .iter()
+            .flat_map(|entry| entry.split_ascii_whitespace())
+    }
+}
+
+/// Every class is compared with the ones before it, which is quadratic in the number of
+/// classes. `N` is the number of `.` shorthands of a single element, and dynamic entries
+/// rarely hold more than a few classes, so this is cheaper than hashing them into a set.
+impl<const N: usize> Render for ClassList<N> {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to_output(&mut FormatterOutput(f))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        for (i, class) in self.classes().enumerate() {
+            if self.classes().take(i).any(|prev| prev == class) {
+                continue;
+            }
+            if i > 0 {
+                out.write_str(" ")?;
+            }
+            out.write_str(class)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClassList;
+    use crate::{Render, html};
+
+    #[test]
+    fn merge_and_dedup() {
+        let mut classes = ClassList::<4>::new();
+        classes.push_static("btn");
+        classes.push(&"btn-large  btn");
+        classes.push(&None::<&str>);
+        classes.push(&"<x>");
+
+        assert_eq!(classes.render().0, "btn btn-large &lt;x&gt;");
+    }
+
+    #[test]
+    fn empty() {
+        let mut classes = ClassList::<2>::new();
+        classes.push(&None::<&str>);
+        classes.push(&" ");
+
+        assert!(classes.non_empty().is_none());
+    }
+
+    #[test]
+    fn merge_branches() {
+        let button = |active: bool, size: u8| {
+            html! {
+                button ."btn" if active { ."active" } id: "b" match size {
+                    0 => { ."small" title: "small" }
+                    _ => {}
+                } {}
+            }
+            .render()
+            .0
+        };
+
+        assert_eq!(button(false, 1), r#"<button id="b" class="btn"></button>"#);
+        assert_eq!(
+            button(true, 0),
+            r#"<button id="b" title="small" class="btn active small"></button>"#
+        );
+    }
+}
@@ -1,20 +1,37 @@
-use crate::Render;
+use crate::{Output, Render, output::OutputWriter, render::single_write_len};
 use std::fmt;
 
-pub(crate) fn escape(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
-    use fmt::Write;
+/// Writes `s` to `w`, escaping HTML special characters.
+///
+/// Runs of characters that don't need escaping are written with a single `write_str`.
+pub(crate) fn escape<W: fmt::Write + ?Sized>(s: &str, w: &mut W) -> fmt::Result {
+    let mut start = 0;
 
-    for ch in s.chars() {
-        match ch {
-            '&' => write!(f, "&amp;")?,
-            '<' => write!(f, "&lt;")?,
-            '>' => write!(f, "&gt;")?,
-            '"' => write!(f, "&quot;")?,
-            ch => f.write_char(ch)?,
-        }
+    // The special characters are ASCII, so `i` is always on a char boundary.
+    for (i, byte) in s.bytes().enumerate() {
+        let escaped = match byte {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' => "&quot;",
+            _ => continue,
+        };
+
+        w.write_str(&s[start..i])?;
+        w.write_str(escaped)?;
+        start = i + 1;
     }
 
-    Ok(())
+    w.write_str(&s[start..])
+}
+
+/// A [`fmt::Write`] adapter that escapes everything written to it.
+struct EscapeWriter<'a, W: ?Sized>(&'a mut W);
+
+impl<W: fmt::Write + ?Sized> fmt::Write for EscapeWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        escape(s, self.0)
+    }
 }
 
 /// Wrapper that escapes HTML special characters.
@@ -25,6 +42,8 @@ pub(crate) fn escape(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
 /// - `>` ==> `&gt;`
 /// - `"` ==> `&quot;`
 ///
+/// [`Render::size_hint`] formats the value to measure it, which is cheap for strings.
+///
 /// # Example
 ///
 /// ```
@@ -36,12 +55,144 @@ pub struct Escaped<T>(pub T);
 
 impl<T: fmt::Display> fmt::Display for Escaped<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        escape(&self.0.to_string(), f)
+        fmt::write(&mut EscapeWriter(f), format_args!("{}", self.0))
     }
 }
 
 impl<T: fmt::Display> Render for Escaped<T> {
     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        escape(&self.0.to_string(), f)
+        fmt::write(&mut EscapeWriter(f), format_args!("{}", self.0))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        fmt::write(
+            &mut EscapeWriter(&mut OutputWriter(out)),
+            format_args!("{}", self.0),
+        )
+    }
+
+    /// The length before escaping, like for `str`, see [`Raw`](crate::Raw).
+    fn size_hint(&self) -> usize {
+        single_write_len(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Escaped, escape};
+    use std::fmt::{self, Write};
+
+    /// The previous implementation, escaping one character at a time.
+    fn reference(s: &str) -> String {
+        let mut out = String::new();
+        for ch in s.chars() {
+            match ch {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                ch => out.push(ch),
+            }
+        }
+        out
+    }
+
+    fn escaped(s: &str) -> String {
+        let mut out = String::new();
+        escape(s, &mut out).unwrap();
+        out
+    }
+
+    const INPUTS: &[&str] = &[
+        "",
+        "plain text",
+        "&",
+        "<>",
+        "\"quoted\"",
+        "a & b < c > d \" e",
+        "&&&<<<>>>\"\"\"",
+        "zażółć <gęślą> jaźń",
+        "🦀 & 🦀",
+        "<script>alert('xss')</script>",
+        "ends with &",
+        "& starts with",
+    ];
+
+    #[test]
+    fn matches_reference() {
+        for input in INPUTS {
+            assert_eq!(escaped(input), reference(input), "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn matches_reference_large() {
+        let input = INPUTS.concat().repeat(1000);
+        assert_eq!(escaped(&input), reference(&input));
+    }
+
+    #[test]
+    fn escaped_display() {
+        for input in INPUTS {
+            assert_eq!(Escaped(input).to_string(), reference(input));
+        }
+
+        // Values written in several pieces are escaped as a whole.
+        let pieces = fmt::from_fn(|f| {
+            f.write_str("<a")?;
+            f.write_char('&')?;
+            write!(f, "{}>", 1)
+        });
+        assert_eq!(Escaped(pieces).to_string(), "&lt;a&amp;1&gt;");
+    }
+
+    /// `html!` escapes literals at compile time, which must match escaping at runtime.
+    #[test]
+    fn matches_compile_time_escaping() {
+        macro_rules! check {
+            ($($input:literal),* $(,)?) => {$(
+                assert_eq!(crate::html_static! { $input }.0, escaped($input));
+                assert_eq!(
+                    crate::html_static! { div title: $input {} }.0,
+                    format!("<div title=\"{}\"></div>", escaped($input)),
+                );
+            )*};
+        }
+
+        // The same inputs as `INPUTS`, the macro needs literals.
+        check!(
+            "",
+            "plain text",
+            "&",
+            "<>",
+            "\"quoted\"",
+            "a & b < c > d \" e",
+            "&&&<<<>>>\"\"\"",
+            "zażółć <gęślą> jaźń",
+            "🦀 & 🦀",
+            "<script>alert('xss')</script>",
+            "ends with &",
+            "& starts with",
+        );
+    }
+
+    #[test]
+    fn counts_writes() {
+        struct Counter(usize);
+
+        impl Write for Counter {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                self.0 += 1;
+                Ok(())
+            }
+        }
+
+        let mut counter = Counter(0);
+        escape(
+            "a long run of text without special characters",
+            &mut counter,
+        )
+        .unwrap();
+        assert_eq!(counter.0, 1);
     }
 }
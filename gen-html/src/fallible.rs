@@ -0,0 +1,60 @@
+//! Functions used by code generated with the [`try_html!`] macro.
+//!
+//! [`try_html!`]: crate::try_html
+
+use crate::{Output, Raw};
+use std::fmt;
+
+/// Renders a template into a new `String`, returning the first error.
+///
+/// A write error is converted with `E: From<fmt::Error>`, so it's carried out like an
+/// error of the template.
+pub fn try_render<E, F>(size_hint: usize, f: F) -> Result<Raw<String>, E>
+where
+    E: From<fmt::Error>,
+    F: FnOnce(&mut dyn Output) -> Result<(), E>,
+{
+    let mut buf = String::with_capacity(size_hint);
+    f(&mut buf)?;
+    Ok(Raw(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Render, try_html};
+    use std::fmt;
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        NotFound,
+        Render,
+    }
+
+    impl From<fmt::Error> for Error {
+        fn from(fmt::Error: fmt::Error) -> Self {
+            Self::Render
+        }
+    }
+
+    struct Failing;
+
+    impl Render for Failing {
+        fn render_to(&self, _: &mut fmt::Formatter) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let find = |found: bool| found.then_some("x").ok_or(Error::NotFound);
+
+        let html: Result<_, Error> = try_html! { p { (find(true)?) } };
+        assert_eq!(html.unwrap().0, "<p>x</p>");
+
+        let html: Result<_, Error> = try_html! { p { (find(false)?) } };
+        assert_eq!(html.unwrap_err(), Error::NotFound);
+
+        let html: Result<_, Error> = try_html! { p { (Failing) } };
+        assert_eq!(html.unwrap_err(), Error::Render);
+    }
+}
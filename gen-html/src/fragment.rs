@@ -0,0 +1,168 @@
+use crate::{Output, Render, output::FormatterOutput};
+use std::fmt;
+
+/// A template containing named fragments.
+///
+/// This `struct` is created by the [`html!`] macro when the template contains
+/// `fragment name { ... }`. It renders the whole template, and [`FragmentFn::fragment`]
+/// renders a single fragment on its own.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{html, Render};
+///
+/// let todos = ["Buy milk", "Walk the dog"];
+///
+/// let page = html! {
+///     h1 { "Todo" }
+///     fragment list {
+///         ul @"todos" {
+///             for todo in todos {
+///                 li { (todo) }
+///             }
+///         }
+///     }
+/// };
+///
+/// // For example, when handling an `HX-Request`.
+/// let partial = page.fragment("list").expect("`list` is a fragment of `page`");
+/// assert!(page.fragment("lsit").is_none());
+/// # assert_eq!(
+/// #     page.render().0,
+/// #     r#"<h1>Todo</h1><ul id="todos"><li>Buy milk</li><li>Walk the dog</li></ul>"#
+/// # );
+/// # assert_eq!(
+/// #     partial.render().0,
+/// #     r#"<ul id="todos"><li>Buy milk</li><li>Walk the dog</li></ul>"#
+/// # );
+/// ```
+///
+/// [`html!`]: crate::html
+pub struct FragmentFn<F> {
+    f: F,
+    names: &'static [&'static str],
+    size_hint: usize,
+}
+
+impl<F> FragmentFn<F> {
+    /// Sets the value returned by [`Render::size_hint`] for the whole template.
+    pub fn with_size_hint(self, size_hint: usize) -> Self {
+        Self { size_hint, ..self }
+    }
+}
+
+impl<F> FragmentFn<F>
+where
+    F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+{
+    /// Returns the fragment called `name`, or [`None`] if the template has no such fragment.
+    ///
+    /// Everything outside of the fragment is skipped, but `let` bindings and control
+    /// structures around it are still evaluated.
+    pub fn fragment(&self, name: &str) -> Option<Fragment<'_, F>> {
+        let name = self.names.iter().find(|n| **n == name)?;
+        Some(Fragment {
+            template: self,
+            name,
+        })
+    }
+
+    /// Returns the names of all fragments of the template, sorted alphabetically.
+    pub fn fragment_names(&self) -> &'static [&'static str] {
+        self.names
+    }
+}
+
+impl<F> fmt::Debug for FragmentFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FragmentFn").finish()
+    }
+}
+
+impl<F> Render for FragmentFn<F>
+where
+    F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+{
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.f)(&mut FormatterOutput(f), None)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        (self.f)(out, None)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+}
+
+impl<F> fmt::Display for FragmentFn<F>
+where
+    F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to(f)
+    }
+}
+
+/// A single fragment of a template.
+///
+/// This `struct` is created by [`FragmentFn::fragment`].
+pub struct Fragment<'a, F> {
+    template: &'a FragmentFn<F>,
+    name: &'static str,
+}
+
+impl<F> Fragment<'_, F> {
+    /// Returns the name of the fragment.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<F> fmt::Debug for Fragment<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fragment")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl<F> Render for Fragment<'_, F>
+where
+    F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+{
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.template.f)(&mut FormatterOutput(f), Some(self.name))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        (self.template.f)(out, Some(self.name))
+    }
+}
+
+impl<F> fmt::Display for Fragment<'_, F>
+where
+    F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to(f)
+    }
+}
+
+/// Creates a [`FragmentFn`] from a function that renders the fragment passed to it,
+/// or the whole template when given [`None`].
+///
+/// `names` lists the fragments rendered by `f`, [`FragmentFn::fragment`] returns [`None`]
+/// for every other name.
+pub fn fragment_fn<F>(names: &'static [&'static str], f: F) -> FragmentFn<F>
+where
+    F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+{
+    FragmentFn {
+        f,
+        names,
+        size_hint: 0,
+    }
+}
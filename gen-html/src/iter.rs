@@ -0,0 +1,183 @@
+use crate::{Output, Render};
+use std::fmt;
+
+/// Extension methods for rendering iterators.
+///
+/// The returned values implement [`Render`], so a list can be written as a single
+/// `(expr)` node inside of [`html!`], or used with [`render_fn`]. Nothing is allocated,
+/// the iterator is cloned every time the value is rendered.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{RenderIterator, html};
+///
+/// let tags = ["rust", "html", "web"];
+/// let path = ["Home", "Blog", "Rust"];
+///
+/// let markup = html! {
+///     nav { (path.iter().render_joined(" / ")) }
+///     p { "Tags: " (tags.iter().render_joined(", ")) }
+///     ul { (tags.iter().render_each(|tag| html! { li { (tag) } })) }
+/// };
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     "<nav>Home / Blog / Rust</nav><p>Tags: rust, html, web</p><ul><li>rust</li><li>html</li><li>web</li></ul>"
+/// # );
+/// ```
+///
+/// [`html!`]: crate::html
+/// [`render_fn`]: crate::render_fn
+pub trait RenderIterator: Iterator + Sized {
+    /// Renders every item using `f`.
+    fn render_each<F, R>(self, f: F) -> RenderEach<Self, F>
+    where
+        F: Fn(Self::Item) -> R,
+        R: Render,
+    {
+        RenderEach { iter: self, f }
+    }
+
+    /// Renders every item, with `separator` between them.
+    fn render_joined<S>(self, separator: S) -> RenderJoined<Self, S>
+    where
+        Self::Item: Render,
+        S: Render,
+    {
+        RenderJoined {
+            iter: self,
+            separator,
+        }
+    }
+}
+
+impl<I: Iterator> RenderIterator for I {}
+
+/// Renders every item of an iterator using a function.
+///
+/// This `struct` is created by [`RenderIterator::render_each`].
+#[derive(Clone)]
+pub struct RenderEach<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F> fmt::Debug for RenderEach<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderEach").finish()
+    }
+}
+
+impl<I, F, R> Render for RenderEach<I, F>
+where
+    I: Iterator + Clone,
+    F: Fn(I::Item) -> R,
+    R: Render,
+{
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.iter
+            .clone()
+            .try_for_each(|item| (self.f)(item).render_to(f))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.iter
+            .clone()
+            .try_for_each(|item| (self.f)(item).render_to_output(out))
+    }
+}
+
+impl<I, F, R> fmt::Display for RenderEach<I, F>
+where
+    I: Iterator + Clone,
+    F: Fn(I::Item) -> R,
+    R: Render,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to(f)
+    }
+}
+
+/// Renders every item of an iterator, separated by a value.
+///
+/// This `struct` is created by [`RenderIterator::render_joined`].
+#[derive(Clone)]
+pub struct RenderJoined<I, S> {
+    iter: I,
+    separator: S,
+}
+
+impl<I, S> fmt::Debug for RenderJoined<I, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderJoined").finish()
+    }
+}
+
+impl<I, S> Render for RenderJoined<I, S>
+where
+    I: Iterator + Clone,
+    I::Item: Render,
+    S: Render,
+{
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut iter = self.iter.clone();
+        let Some(first) = iter.next() else {
+            return Ok(());
+        };
+
+        first.render_to(f)?;
+        iter.try_for_each(|item| {
+            self.separator.render_to(f)?;
+            item.render_to(f)
+        })
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        let mut iter = self.iter.clone();
+        let Some(first) = iter.next() else {
+            return Ok(());
+        };
+
+        first.render_to_output(out)?;
+        iter.try_for_each(|item| {
+            self.separator.render_to_output(out)?;
+            item.render_to_output(out)
+        })
+    }
+}
+
+impl<I, S> fmt::Display for RenderJoined<I, S>
+where
+    I: Iterator + Clone,
+    I::Item: Render,
+    S: Render,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderIterator;
+    use crate::{Raw, Render, render_fn};
+
+    #[test]
+    fn joined() {
+        let empty: [&str; 0] = [];
+        assert_eq!(empty.iter().render_joined(", ").render().0, "");
+        assert_eq!(["a"].iter().render_joined(", ").render().0, "a");
+        assert_eq!(
+            ["a", "<b>"].iter().render_joined(Raw("<br>")).render().0,
+            "a<br>&lt;b&gt;"
+        );
+    }
+
+    #[test]
+    fn each_renders_again() {
+        let items = (1..=3).render_each(|i| render_fn(move |f| write!(f, "[{i}]")));
+
+        assert_eq!(items.render().0, "[1][2][3]");
+        assert_eq!(items.render().0, "[1][2][3]");
+    }
+}
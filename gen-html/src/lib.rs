@@ -43,8 +43,25 @@
 //! # assert_eq!(markup.render().0, "<span>1</span><span>2</span><span>3</span>");
 //! ```
 
+// Lets unit tests use the macros, which refer to `::gen_html`.
+#[cfg(test)]
+extern crate self as gen_html;
+
+mod async_render;
+mod attributes;
+mod block;
+mod class;
 mod escape;
+mod fallible;
+mod fragment;
+mod iter;
+mod markup;
+mod once;
+mod output;
+mod props;
 mod render;
+mod slot;
+mod tag;
 mod value;
 mod web;
 
@@ -104,6 +121,21 @@ pub const DOCTYPE: Raw<&str> = Raw("<!DOCTYPE html>");
 /// # assert_eq!(markup.to_string(), "First line<br>Second line");
 /// ```
 ///
+/// Elements whose name is picked at runtime are written using `tag (expr)`, where `expr`
+/// is a [`Tag`]. The body of a void element is skipped, see [`Tag`](Tag#void-elements).
+///
+/// ```
+/// # use gen_html::{Tag, html};
+/// let tag = Tag::Button;
+///
+/// # let markup =
+/// html! {
+///     tag (tag) ."link" { "Open" }
+/// }
+/// # ;
+/// # assert_eq!(markup.to_string(), r#"<button class="link">Open</button>"#);
+/// ```
+///
 /// # Attributes
 ///
 /// Attributes are written using `name: value`.
@@ -136,6 +168,55 @@ pub const DOCTYPE: Raw<&str> = Raw("<!DOCTYPE html>");
 /// # );
 /// ```
 ///
+/// Attribute names are checked against the element they are used on. Global attributes,
+/// event handlers and custom names containing a `-`, like `data-*`, `aria-*` or htmx's
+/// `hx-get`, are allowed everywhere.
+///
+/// ```compile_fail
+/// # use gen_html::html;
+/// html! {
+///     a hreff: "/about" { "About" }
+/// };
+/// ```
+///
+/// Several attributes can be toggled together using `if` and `match`.
+///
+/// ```
+/// # use gen_html::html;
+/// let disabled = true;
+///
+/// # let markup =
+/// html! {
+///     button if disabled { disabled aria_disabled: "true" } else { title: "Submit" } {
+///         "Submit"
+///     }
+/// }
+/// # ;
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     r#"<button disabled aria-disabled="true">Submit</button>"#
+/// # );
+/// ```
+///
+/// Attributes from an [`Attributes`] collection, or any iterator of `(name, value)` pairs,
+/// can be spread onto an element using `..(attrs)`. Names of spread attributes are checked
+/// at runtime.
+///
+/// ```
+/// # use gen_html::html;
+/// let extra = [("class", "large"), ("title", "Send it")];
+///
+/// # let markup =
+/// html! {
+///     button ."btn" title: "Submit" ..(extra) { "Submit" }
+/// }
+/// # ;
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     r#"<button class="btn large" title="Send it">Submit</button>"#
+/// # );
+/// ```
+///
 /// # Shorthand syntax
 ///
 /// Instead of writing `id` and `class` you may use `@` and `.` respectively.
@@ -156,6 +237,27 @@ pub const DOCTYPE: Raw<&str> = Raw("<!DOCTYPE html>");
 /// # );
 /// ```
 ///
+/// Multiple `.` shorthands are merged into a single `class` attribute, without duplicates.
+/// Append `[cond]` to include a class only when `cond` is `true`. Shorthands in the
+/// branches of an attribute `if` or `match` are merged too, the `class` attribute is
+/// then written after the other attributes.
+///
+/// ```
+/// # use gen_html::html;
+/// let active = true;
+/// let size = "large";
+///
+/// # let markup =
+/// html! {
+///     a ."tab" ."active"[active] ."disabled"[!active] .(size) href: "/" { "Home" }
+/// }
+/// # ;
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     r#"<a class="tab active large" href="/">Home</a>"#
+/// # );
+/// ```
+///
 /// # Inserting expressions
 ///
 /// Use `(expr)` to insert any Rust expression implementing [`Render`].
@@ -172,6 +274,23 @@ pub const DOCTYPE: Raw<&str> = Raw("<!DOCTYPE html>");
 /// # assert_eq!(markup.to_string(), "<p>Hello Alice!</p>");
 /// ```
 ///
+/// [`None`] renders nothing, and slices, [`Vec`]s and tuples render their elements one
+/// after another.
+///
+/// ```
+/// # use gen_html::html;
+/// let nickname: Option<&str> = None;
+/// let tags = vec!["#rust", " #html"];
+///
+/// # let markup =
+/// html! {
+///     p { "Alice" (nickname) }
+///     p { (tags) }
+/// }
+/// # ;
+/// # assert_eq!(markup.to_string(), "<p>Alice</p><p>#rust #html</p>");
+/// ```
+///
 /// Expressions that implement [`Value`] may be used inside attributes. See its documentation for more details.
 ///
 /// ```
@@ -190,6 +309,150 @@ pub const DOCTYPE: Raw<&str> = Raw("<!DOCTYPE html>");
 /// # );
 /// ```
 ///
+/// # Components
+///
+/// Names starting with an uppercase letter (`Card`) or written as a path (`ui::Card`) are
+/// components. Attributes are passed as props and the body is passed as the `children`
+/// prop. Props are set through the builder generated by [`#[component]`](component), so
+/// components with props must be defined with it.
+///
+/// ```
+/// use gen_html::{component, html, Render};
+///
+/// #[component]
+/// fn card(title: &str, children: impl Render) -> impl Render {
+///     html! {
+///         div ."card" {
+///             h2 { (title) }
+///             (children)
+///         }
+///     }
+/// }
+///
+/// # let markup =
+/// html! {
+///     Card title: "Hello" {
+///         p { "world" }
+///     }
+/// }
+/// # ;
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     r#"<div class="card"><h2>Hello</h2><p>world</p></div>"#
+/// # );
+/// ```
+///
+/// Missing and unknown props are reported at compile time.
+///
+/// ```compile_fail
+/// # use gen_html::{component, html, Render};
+/// # #[component]
+/// # fn card(title: &str, children: impl Render) -> impl Render { html! {} }
+/// html! {
+///     Card titel: "Hello" { }
+/// };
+/// ```
+///
+/// Plain functions and tuple structs are called with arguments in parentheses instead. The
+/// body, if there is one, is passed as the last argument.
+///
+/// ```
+/// use gen_html::{html, Render};
+///
+/// mod ui {
+///     use gen_html::{html, Render};
+///
+///     pub fn panel(title: &str, children: impl Render) -> impl Render {
+///         html! {
+///             section {
+///                 h2 { (title) }
+///                 (children)
+///             }
+///         }
+///     }
+/// }
+///
+/// struct Badge(u32);
+///
+/// # impl Render for Badge {
+/// #     fn render_to(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// #         write!(f, "<span class=\"badge\">{}</span>", self.0)
+/// #     }
+/// # }
+/// # let markup =
+/// html! {
+///     ui::panel("Inbox") {
+///         Badge(3);
+///     }
+/// }
+/// # ;
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     r#"<section><h2>Inbox</h2><span class="badge">3</span></section>"#
+/// # );
+/// ```
+///
+/// # Fragments
+///
+/// `fragment name { ... }` marks a part of the template that can be rendered on its own,
+/// see [`FragmentFn`].
+///
+/// # Static templates
+///
+/// Static content is escaped at compile time and written with a single call. Templates
+/// without any dynamic content can be rendered entirely at compile time with
+/// [`html_static!`], which evaluates to a [`Raw<&'static str>`](Raw) that can be used in
+/// `const` and `static` items.
+///
+/// ```
+/// use gen_html::{Raw, html_static};
+///
+/// const FOOTER: Raw<&str> = html_static! {
+///     footer { p { "Made with <3" } }
+/// };
+/// # assert_eq!(FOOTER.0, "<footer><p>Made with &lt;3</p></footer>");
+/// ```
+///
+/// # Capturing variables
+///
+/// By default, the template moves the variables it uses and can be rendered many times,
+/// so it cannot consume them. Starting the template with `once;` creates a
+/// [`RenderOnce`], which can consume its variables but can only be rendered once.
+/// Starting the template with `ref;` borrows the variables instead of moving them, so
+/// they can still be used after the template is created.
+///
+/// ```
+/// use gen_html::html;
+///
+/// let tags = vec!["rust", "html"];
+///
+/// let list = html! {
+///     ref;
+///     for tag in &tags {
+///         span { (tag) }
+///     }
+/// };
+/// # assert_eq!(list.to_string(), "<span>rust</span><span>html</span>");
+/// println!("{list} ({} tags)", tags.len());
+/// ```
+///
+/// A `for` loop that consumes a captured variable without `once;` fails to compile with
+/// "`Vec<&str>` is moved out of the template by this `for` loop", and a note suggesting
+/// `once;`. Other ways of consuming a variable fail with "expected a closure that
+/// implements the `Fn` trait, but this closure only implements `FnOnce`". A template that
+/// uses a variable after it was moved fails with "borrow of moved value", and needs
+/// `ref;`.
+///
+/// ```compile_fail
+/// # use gen_html::html;
+/// let tags = vec!["rust", "html"];
+/// html! {
+///     for tag in tags {
+///         span { (tag) }
+///     }
+/// };
+/// ```
+///
 /// # Control structures
 ///
 /// ## `if`
@@ -291,6 +554,202 @@ pub const DOCTYPE: Raw<&str> = Raw("<!DOCTYPE html>");
 /// ```
 pub use gen_html_proc::html;
 
+/// Render a template whose expressions may fail.
+///
+/// `try_html!` accepts the same syntax as [`html!`], but expressions, conditions and `let`
+/// bindings may use `?`. The template is rendered right away into a
+/// `Result<Raw<String>, E>`, where `E` is the error type of the template, and rendering
+/// stops at the first error. `E` must implement `From<fmt::Error>`, which is used when a
+/// [`Render`] implementation fails.
+///
+/// `?` cannot be used inside of a component's body, and templates cannot contain fragments.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{Raw, try_html};
+/// use std::{collections::HashMap, fmt};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Error {
+///     NotFound(u32),
+///     Render,
+/// }
+///
+/// impl From<fmt::Error> for Error {
+///     fn from(_: fmt::Error) -> Self {
+///         Error::Render
+///     }
+/// }
+///
+/// fn name(users: &HashMap<u32, &'static str>, id: u32) -> Result<&'static str, Error> {
+///     users.get(&id).copied().ok_or(Error::NotFound(id))
+/// }
+///
+/// let users = HashMap::from([(1, "Alice"), (2, "Bob")]);
+///
+/// let page: Result<Raw<String>, Error> = try_html! {
+///     ul {
+///         for id in [1, 2] {
+///             li { (name(&users, id)?) }
+///         }
+///     }
+/// };
+/// assert_eq!(page.unwrap().0, "<ul><li>Alice</li><li>Bob</li></ul>");
+///
+/// let page: Result<Raw<String>, Error> = try_html! {
+///     p { (name(&users, 3)?) }
+/// };
+/// assert_eq!(page.unwrap_err(), Error::NotFound(3));
+/// ```
+pub use gen_html_proc::try_html;
+
+/// Render a template whose expressions may use `.await`.
+///
+/// `html_async!` accepts the same syntax as [`html!`], but expressions, conditions and `let`
+/// bindings may use `.await`. It evaluates to an [`AsyncRenderFn`], which is rendered with
+/// the methods of [`AsyncRender`]. With the `axum` feature, [`AsyncRender::respond`] turns
+/// it into a response inside of an async handler.
+///
+/// `.await` cannot be used inside of a component's body, and templates cannot contain
+/// fragments. Other async templates can be included by awaiting them, eg.
+/// `(sidebar.render_async().await)`.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{AsyncRender, html_async};
+///
+/// struct Post {
+///     title: String,
+/// }
+///
+/// async fn load_posts() -> Vec<Post> {
+///     vec![Post { title: "Hello".into() }, Post { title: "<World>".into() }]
+/// }
+///
+/// async fn page() -> String {
+///     let page = html_async! {
+///         let posts = load_posts().await;
+///         ul {
+///             for post in &posts {
+///                 li { (post.title) }
+///             }
+///         }
+///     };
+///     page.render_async().await.0
+/// }
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     match std::pin::pin!(future).poll(&mut cx) {
+/// #         std::task::Poll::Ready(output) => output,
+/// #         std::task::Poll::Pending => unreachable!(),
+/// #     }
+/// # }
+/// # assert_eq!(block_on(page()), "<ul><li>Hello</li><li>&lt;World&gt;</li></ul>");
+/// ```
+pub use gen_html_proc::html_async;
+
+/// Render a template at compile time.
+///
+/// `html_static!` accepts the same syntax as [`html!`], but the template cannot contain any
+/// dynamic content. It evaluates to a [`Raw<&'static str>`](Raw), so it costs nothing at
+/// runtime and can be used in `const` and `static` items.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{Raw, html_static};
+///
+/// static ICON: Raw<&str> = html_static! {
+///     span ."icon" { "&" }
+/// };
+/// # assert_eq!(ICON.0, r#"<span class="icon">&amp;</span>"#);
+/// ```
+///
+/// Using an expression is a compile error.
+///
+/// ```compile_fail
+/// # use gen_html::html_static;
+/// let name = "world";
+/// html_static! {
+///     p { "Hello " (name) }
+/// };
+/// ```
+pub use gen_html_proc::html_static;
+
+/// Define a component that can be used inside the [`html!`] macro.
+///
+/// `#[component]` generates a props struct named after the function in `PascalCase`,
+/// with a builder that has a method for every parameter. Parameters of type [`Option<T>`]
+/// and parameters marked with `#[prop(default)]` or `#[prop(default = expr)]` are optional,
+/// all other parameters are required. Parameters marked with `#[prop(slot)]`, usually of
+/// type [`Slot`], are optional and can be filled using `slot name { ... }` inside of the
+/// component's body. Parameters marked with `#[prop(block)]`, usually of type [`Block`],
+/// are optional too, they make the component a layout with overridable blocks.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{component, html, Render};
+///
+/// #[component]
+/// fn button(
+///     label: &str,
+///     title: Option<&str>,
+///     #[prop(default = "primary")] variant: &str,
+/// ) -> impl Render {
+///     html! {
+///         button .(variant) title: (title) { (label) }
+///     }
+/// }
+///
+/// let markup = html! {
+///     Button label: "Save";
+///     Button label: "Delete" title: "Delete forever" variant: "danger";
+/// };
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     r#"<button class="primary">Save</button><button class="danger" title="Delete forever">Delete</button>"#
+/// # );
+///
+/// // Components can also be used outside of `html!`.
+/// let save = Button::builder().label("Save").build();
+/// # assert_eq!(save.render().0, r#"<button class="primary">Save</button>"#);
+/// ```
+///
+/// Omitting a required prop is a compile error naming the missing prop.
+///
+/// ```compile_fail
+/// # use gen_html::{component, html, Render};
+/// # #[component]
+/// # fn button(label: &str) -> impl Render { html! { button { (label) } } }
+/// html! {
+///     Button;
+/// };
+/// ```
+pub use gen_html_proc::component;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::class::ClassList;
+    pub use crate::fallible::try_render;
+    pub use crate::once::assert_reusable;
+    pub use crate::props::{Optional, Set, Unset, UseDefault};
+    pub use crate::render::output_fn;
+    pub use crate::value::{insert_value, render_value_to};
+}
+
+pub use async_render::{AsyncRender, AsyncRenderFn, async_render_fn};
+pub use attributes::{Attributes, Spread};
+pub use block::Block;
 pub use escape::Escaped;
-pub use render::{Raw, Render, RenderFn, render_fn};
+pub use fragment::{Fragment, FragmentFn, fragment_fn};
+pub use iter::{RenderEach, RenderIterator, RenderJoined};
+pub use markup::{LocalMarkup, Markup};
+pub use once::{RenderOnce, render_once};
+pub use output::{IoOutput, Output};
+pub use render::{IsoDuration, Raw, Render, RenderError, RenderFn, render_fn};
+pub use slot::Slot;
+pub use tag::Tag;
 pub use value::Value;
@@ -0,0 +1,290 @@
+use crate::{Escaped, FragmentFn, Output, Raw, Render, RenderFn, RenderOnce};
+use std::{any::Any, fmt, rc::Rc, sync::Arc};
+
+/// A type-erased template.
+///
+/// Every template created with the [`html!`] macro has its own type. `Markup` can hold
+/// any of them, so templates can be stored in a `Vec`, a struct field or a `HashMap`.
+/// It is `Send + Sync`, and cloning it only increments a reference count.
+///
+/// Use [`Markup::new`], [`Render::into_markup`] or [`From`] to create it. Templates that
+/// aren't `Send + Sync` or `'static`, eg. ones that borrow local variables or capture an
+/// [`Rc`], can be stored in a [`LocalMarkup`] instead.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{Markup, Render, html};
+///
+/// let count = 3;
+/// let widgets: Vec<Markup> = vec![
+///     html! { h2 { "Inbox" } }.into_markup(),
+///     html! { p { (count) " new messages" } }.into_markup(),
+/// ];
+///
+/// let page = html! {
+///     for widget in &widgets {
+///         section { (widget) }
+///     }
+/// };
+/// # assert_eq!(
+/// #     page.render().0,
+/// #     "<section><h2>Inbox</h2></section><section><p>3 new messages</p></section>"
+/// # );
+/// ```
+///
+/// [`html!`]: crate::html
+#[derive(Clone)]
+pub struct Markup(Arc<dyn Render + Send + Sync>);
+
+impl Markup {
+    /// Erases the type of `html`.
+    ///
+    /// `Markup` itself is returned as is, without adding another layer of indirection.
+    pub fn new<R>(html: R) -> Self
+    where
+        R: Render + Send + Sync + 'static,
+    {
+        let mut html = Some(html);
+        if let Some(markup) = (&mut html as &mut dyn Any).downcast_mut::<Option<Self>>() {
+            return markup.take().unwrap();
+        }
+
+        Self(Arc::new(html.unwrap()))
+    }
+}
+
+impl Default for Markup {
+    /// Returns markup that renders nothing.
+    fn default() -> Self {
+        Self::new(Raw(""))
+    }
+}
+
+impl fmt::Debug for Markup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Markup").finish()
+    }
+}
+
+impl Render for Markup {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.0.render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+}
+
+impl fmt::Display for Markup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.render_to(f)
+    }
+}
+
+/// A type-erased template that isn't `Send + Sync` and may borrow local variables.
+///
+/// `LocalMarkup` works like [`Markup`], but it's backed by an [`Rc`], so it can hold any
+/// template that lives for `'a`.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{LocalMarkup, Markup, Render, html};
+/// use std::rc::Rc;
+///
+/// let title = Rc::new(String::from("Inbox"));
+/// let widgets: Vec<LocalMarkup> = vec![
+///     html! { h2 { (title) } }.into(),
+///     Markup::new("3 new messages").into(),
+/// ];
+///
+/// let page = html! {
+///     for widget in &widgets {
+///         section { (widget) }
+///     }
+/// };
+/// # assert_eq!(
+/// #     page.render().0,
+/// #     "<section><h2>Inbox</h2></section><section>3 new messages</section>"
+/// # );
+/// ```
+#[derive(Clone)]
+pub struct LocalMarkup<'a>(Rc<dyn Render + 'a>);
+
+impl<'a> LocalMarkup<'a> {
+    /// Erases the type of `html`.
+    pub fn new<R: Render + 'a>(html: R) -> Self {
+        Self(Rc::new(html))
+    }
+}
+
+impl Default for LocalMarkup<'_> {
+    /// Returns markup that renders nothing.
+    fn default() -> Self {
+        Self::new(Raw(""))
+    }
+}
+
+impl fmt::Debug for LocalMarkup<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalMarkup").finish()
+    }
+}
+
+impl Render for LocalMarkup<'_> {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.0.render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+}
+
+impl fmt::Display for LocalMarkup<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.render_to(f)
+    }
+}
+
+impl From<Markup> for LocalMarkup<'_> {
+    fn from(markup: Markup) -> Self {
+        Self::new(markup)
+    }
+}
+
+impl From<String> for LocalMarkup<'_> {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<'a> From<&'a str> for LocalMarkup<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<'a, F: 'a> From<RenderOnce<F>> for LocalMarkup<'a>
+where
+    RenderOnce<F>: Render,
+{
+    fn from(html: RenderOnce<F>) -> Self {
+        Self::new(html)
+    }
+}
+
+/// Implements `From` for `Markup` and `LocalMarkup`, for types that are `Send + Sync`
+/// when their parameters are.
+macro_rules! impl_from {
+    ($(impl<$($param:ident $(: ?$sized:ident)?),*> $ty:ty;)*) => {$(
+        impl<$($param $(: ?$sized)?),*> From<$ty> for Markup
+        where
+            $ty: Render + Send + Sync + 'static,
+        {
+            fn from(html: $ty) -> Self {
+                Self::new(html)
+            }
+        }
+
+        impl<'a, $($param $(: ?$sized)?),*> From<$ty> for LocalMarkup<'a>
+        where
+            $ty: Render + 'a,
+        {
+            fn from(html: $ty) -> Self {
+                Self::new(html)
+            }
+        }
+    )*};
+}
+
+impl_from! {
+    impl<F, O: ?Sized> RenderFn<F, O>;
+    impl<F> FragmentFn<F>;
+    impl<T> Raw<T>;
+    impl<T> Escaped<T>;
+}
+
+impl From<String> for Markup {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&'static str> for Markup {
+    fn from(text: &'static str) -> Self {
+        Self::new(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalMarkup, Markup};
+    use crate::{Escaped, Raw, Render, html, render_fn};
+    use std::{rc::Rc, sync::Arc};
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Markup>();
+    }
+
+    #[test]
+    fn heterogeneous() {
+        let items = [
+            Markup::new("a & b"),
+            Markup::new(Raw("<br>")),
+            Markup::new(render_fn(|f| f.write_str("<hr>")).with_size_hint(4)),
+            Markup::default(),
+        ];
+        let html: String = items.iter().map(|item| item.render().0).collect();
+
+        assert_eq!(html, "a &amp; b<br><hr>");
+        assert_eq!(items[2].size_hint(), 4);
+    }
+
+    #[test]
+    fn no_double_boxing() {
+        let markup = Markup::new("text");
+        let wrapped = Markup::new(markup.clone());
+
+        assert!(Arc::ptr_eq(&markup.0, &wrapped.0));
+    }
+
+    #[test]
+    fn conversions() {
+        let items: [Markup; 4] = [
+            html! { b { "a" } }.into(),
+            Raw("<br>").into(),
+            Escaped("<").into(),
+            String::from("&").into(),
+        ];
+        let html: String = items.iter().map(|item| item.render().0).collect();
+
+        assert_eq!(html, "<b>a</b><br>&lt;&amp;");
+    }
+
+    #[test]
+    fn local() {
+        let shared = Rc::new(String::from("shared"));
+        let local = String::from("local");
+        let items: [LocalMarkup; 3] = [
+            html! { (shared) }.into(),
+            html! { ref; " " (local) }.into(),
+            Markup::new(" markup").into(),
+        ];
+        let html: String = items.iter().map(|item| item.render().0).collect();
+
+        assert_eq!(html, "shared local markup");
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Output, Raw, Render, output::FormatterOutput};
+use std::{cell::Cell, fmt};
+
+/// A template that can be rendered only once.
+///
+/// This `struct` is created by the [`html!`] macro when the template starts with `once;`.
+/// The template is allowed to consume the values it captures, like an iterator or a
+/// `Vec` moved into a `for` loop. Use [`RenderOnce::render_once`] to render it, or
+/// include it in another template.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::html;
+///
+/// let names = vec![String::from("Alice"), String::from("Bob")];
+///
+/// let list = html! {
+///     once;
+///     ul {
+///         for name in names {
+///             li { (name) }
+///         }
+///     }
+/// };
+/// # assert_eq!(list.render_once().0, "<ul><li>Alice</li><li>Bob</li></ul>");
+/// ```
+///
+/// # Errors
+///
+/// Rendering through [`Render`] or [`Display`](fmt::Display) returns [`fmt::Error`] if
+/// the template was already rendered, so [`Render::render`] panics and the web
+/// integrations respond with `500 Internal Server Error`.
+///
+/// [`html!`]: crate::html
+pub struct RenderOnce<F> {
+    f: Cell<Option<F>>,
+    size_hint: usize,
+}
+
+impl<F> RenderOnce<F> {
+    /// Sets the value returned by [`Render::size_hint`].
+    pub fn with_size_hint(self, size_hint: usize) -> Self {
+        Self { size_hint, ..self }
+    }
+}
+
+impl<F> RenderOnce<F>
+where
+    F: FnOnce(&mut dyn Output) -> fmt::Result,
+{
+    /// Consumes the template and converts it to a `Raw<String>`.
+    pub fn render_once(self) -> Raw<String> {
+        self.render()
+    }
+
+    fn call(&self, out: &mut dyn Output) -> fmt::Result {
+        let render = self.f.take().ok_or(fmt::Error)?;
+        render(out)
+    }
+}
+
+impl<F> fmt::Debug for RenderOnce<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderOnce").finish()
+    }
+}
+
+impl<F> Render for RenderOnce<F>
+where
+    F: FnOnce(&mut dyn Output) -> fmt::Result,
+{
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.call(&mut FormatterOutput(f))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.call(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+}
+
+impl<F> fmt::Display for RenderOnce<F>
+where
+    F: FnOnce(&mut dyn Output) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.call(&mut FormatterOutput(f))
+    }
+}
+
+/// Creates a type whose [`Render`] impl is provided with the function `f`, which is
+/// called at most once.
+pub fn render_once<F>(f: F) -> RenderOnce<F>
+where
+    F: FnOnce(&mut dyn Output) -> fmt::Result,
+{
+    RenderOnce {
+        f: Cell::new(Some(f)),
+        size_hint: 0,
+    }
+}
+
+/// Values that a template rendered more than once can iterate over without consuming
+/// them, used by the [`html!`] macro to suggest `once;`.
+///
+/// [`html!`]: crate::html
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is moved out of the template by this `for` loop",
+    label = "moved by this `for` loop",
+    note = "the template can be rendered more than once, so it cannot consume the variables it captures",
+    note = "start the template with `once;`, or iterate over a reference"
+)]
+pub trait Reusable {}
+
+#[diagnostic::do_not_recommend]
+impl<T: Copy> Reusable for T {}
+
+/// Checks that a `for` loop of a template doesn't consume `value`, see `Reusable`.
+pub fn assert_reusable<T: Reusable>(_value: &T) {}
+
+#[cfg(test)]
+mod tests {
+    use super::render_once;
+    use crate::Render;
+
+    #[test]
+    fn render_twice() {
+        let items = vec![String::from("a")];
+        let markup = render_once(move |out| {
+            for item in items {
+                item.render_to_output(out)?;
+            }
+            Ok(())
+        });
+
+        assert_eq!(markup.render().0, "a");
+        assert!(markup.try_render().is_err());
+    }
+}
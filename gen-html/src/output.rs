@@ -0,0 +1,170 @@
+use std::{fmt, io};
+
+/// A destination for rendered HTML.
+///
+/// `Output` is implemented for [`String`] and [`Vec<u8>`], and [`IoOutput`] adapts any
+/// [`io::Write`], like a file or a socket. Use [`Render::render_to_output`] to render
+/// into it. Templates created with [`html!`] write to it directly, without going through
+/// a [`fmt::Formatter`].
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{Render, html};
+///
+/// let mut buf = String::new();
+/// for i in 1..=2 {
+///     buf.clear();
+///     html! { p { (i) } }.render_to_output(&mut buf).unwrap();
+///     # assert_eq!(buf, format!("<p>{i}</p>"));
+/// }
+/// ```
+///
+/// [`Render::render_to_output`]: crate::Render::render_to_output
+/// [`html!`]: crate::html
+pub trait Output {
+    /// Writes already escaped HTML.
+    fn write_str(&mut self, s: &str) -> fmt::Result;
+}
+
+impl Output for String {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl Output for Vec<u8> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<O: Output + ?Sized> Output for &mut O {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        O::write_str(self, s)
+    }
+}
+
+/// An [`Output`] writing to an [`io::Write`].
+///
+/// The output isn't buffered, wrap the writer in an [`io::BufWriter`] when writing to
+/// a file or a socket. If writing fails, rendering stops with [`fmt::Error`] and the
+/// underlying error can be retrieved with [`IoOutput::take_error`].
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{IoOutput, Render, html};
+/// use std::io::{self, BufWriter};
+///
+/// let mut out = IoOutput::new(BufWriter::new(io::sink()));
+/// html! { h1 { "Hello" } }.render_to_output(&mut out).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct IoOutput<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoOutput<W> {
+    /// Creates an adapter writing to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Returns the error that stopped rendering, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::Write> Output for IoOutput<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Adapts an [`Output`] to [`fmt::Write`], which is needed to obtain a [`fmt::Formatter`].
+pub(crate) struct OutputWriter<'a>(pub &'a mut dyn Output);
+
+impl fmt::Write for OutputWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+/// Adapts a [`fmt::Formatter`] to [`Output`], used to implement [`Render::render_to`]
+/// for types that write to an [`Output`].
+///
+/// [`Render::render_to`]: crate::Render::render_to
+pub(crate) struct FormatterOutput<'a, 'b>(pub &'a mut fmt::Formatter<'b>);
+
+impl Output for FormatterOutput<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+/// Writes `value` formatted with [`Display`](fmt::Display) to `out`.
+pub(crate) fn write_display(out: &mut dyn Output, value: &dyn fmt::Display) -> fmt::Result {
+    fmt::write(&mut OutputWriter(out), format_args!("{value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IoOutput;
+    use crate::{Render, render_fn};
+    use std::io;
+
+    #[test]
+    fn outputs() {
+        let markup = render_fn(|f| {
+            f.write_str("<p>")?;
+            "a & b".render_to(f)?;
+            1.render_to(f)?;
+            f.write_str("</p>")
+        });
+        let expected = "<p>a &amp; b1</p>";
+
+        let mut string = String::from("keep ");
+        markup.render_to_output(&mut string).unwrap();
+        assert_eq!(string, format!("keep {expected}"));
+
+        let mut bytes = Vec::new();
+        markup.render_to_output(&mut bytes).unwrap();
+        assert_eq!(bytes, expected.as_bytes());
+
+        let mut io = IoOutput::new(Vec::new());
+        markup.render_to_output(&mut io).unwrap();
+        assert_eq!(io.into_inner(), expected.as_bytes());
+    }
+
+    #[test]
+    fn io_errors() {
+        struct Broken;
+
+        impl io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken pipe"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut out = IoOutput::new(Broken);
+        assert!("text".render_to_output(&mut out).is_err());
+        assert_eq!(out.take_error().unwrap().to_string(), "broken pipe");
+    }
+}
@@ -0,0 +1,31 @@
+//! Types used by code generated with the [`component`] macro.
+//!
+//! [`component`]: crate::component
+
+/// A required prop that was not set.
+pub struct Unset;
+
+/// An optional prop that was not set.
+pub struct UseDefault;
+
+/// A prop that was set.
+///
+/// Required props are read through a trait generated by the macro for each of them,
+/// so that the error for a missing prop names it.
+pub struct Set<T>(pub T);
+
+pub trait Optional<T> {
+    fn into_prop_or(self, default: impl FnOnce() -> T) -> T;
+}
+
+impl<T> Optional<T> for Set<T> {
+    fn into_prop_or(self, _: impl FnOnce() -> T) -> T {
+        self.0
+    }
+}
+
+impl<T> Optional<T> for UseDefault {
+    fn into_prop_or(self, default: impl FnOnce() -> T) -> T {
+        default()
+    }
+}
@@ -1,7 +1,21 @@
-use crate::escape::escape;
+use crate::{
+    Escaped, Markup,
+    escape::escape,
+    output::{FormatterOutput, IoOutput, Output, OutputWriter, write_display},
+};
 use std::{
     borrow::Cow,
+    ffi::{OsStr, OsString},
     fmt::{self, Arguments},
+    io,
+    marker::PhantomData,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::{
+        NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    },
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
 /// Trait for safely rendering HTML content.
@@ -49,6 +63,28 @@ pub trait Render {
     /// [`Formatter`]: std::fmt::Formatter
     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result;
 
+    /// Returns an estimate of the length of the rendered HTML, in bytes.
+    ///
+    /// It is used to preallocate buffers, eg. by [`Render::render`]. Templates created with
+    /// the [`html!`] macro return the length of their static content plus an estimate for
+    /// the dynamic parts, counting the longest branch of an `if` or `match` and a few
+    /// iterations of a `for` loop. The default implementation returns `0`.
+    ///
+    /// ```
+    /// # use gen_html::{html, Render};
+    /// let name = "Alice";
+    /// let markup = html! {
+    ///     p { "Hello " (name) }
+    /// };
+    ///
+    /// assert!(markup.size_hint() >= "<p>Hello </p>".len());
+    /// ```
+    ///
+    /// [`html!`]: crate::html
+    fn size_hint(&self) -> usize {
+        0
+    }
+
     /// Converts the given value to a `Raw<String>`.
     ///
     /// ```
@@ -56,11 +92,103 @@ pub trait Render {
     /// let content = "<this is escaped>";
     /// assert_eq!(content.render().0, "&lt;this is escaped&gt;");
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Render::render_to`] returns [`Err`], use [`Render::try_render`] to handle it.
     fn render(&self) -> Raw<String> {
-        use std::fmt::Write;
-        let mut buf = String::new();
-        write!(&mut buf, "{}", render_fn(|f| self.render_to(f))).expect("render_to returned Err");
-        Raw(buf)
+        self.try_render().expect("render_to returned Err")
+    }
+
+    /// Converts the given value to a `Raw<String>`, returning [`Err`] if
+    /// [`Render::render_to`] fails.
+    fn try_render(&self) -> Result<Raw<String>, fmt::Error> {
+        let mut buf = String::with_capacity(self.size_hint());
+        self.render_to_output(&mut buf)?;
+        Ok(Raw(buf))
+    }
+
+    /// Appends the rendered HTML to `buf`, which allows reusing its allocation.
+    ///
+    /// ```
+    /// # use gen_html::{Render, html};
+    /// let mut buf = String::new();
+    /// for name in ["Alice", "Bob"] {
+    ///     buf.clear();
+    ///     html! { p { (name) } }.render_into(&mut buf).unwrap();
+    ///     # assert_eq!(buf, format!("<p>{name}</p>"));
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if [`Render::render_to`] fails, `buf` may then contain partial output.
+    fn render_into(&self, buf: &mut String) -> fmt::Result {
+        buf.reserve(self.size_hint());
+        self.render_to_output(buf)
+    }
+
+    /// Writes the rendered HTML to `writer`, eg. a file or a socket.
+    ///
+    /// The output isn't buffered, consider wrapping `writer` in an [`io::BufWriter`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if writing fails or [`Render::render_to`] fails.
+    ///
+    /// [`io::BufWriter`]: std::io::BufWriter
+    fn write_to<W: io::Write>(&self, writer: W) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        let mut out = IoOutput::new(writer);
+        self.render_to_output(&mut out).map_err(|fmt::Error| {
+            out.take_error()
+                .unwrap_or_else(|| io::Error::other("render_to returned Err"))
+        })
+    }
+
+    /// Renders HTML to the given [`Output`], eg. a reused [`String`] or an [`IoOutput`].
+    ///
+    /// Templates created with the [`html!`] macro and the types implementing `Render` in
+    /// this crate write to `out` directly. The default implementation calls
+    /// [`Render::render_to`] with a [`Formatter`] writing to `out`, override it to skip the
+    /// formatting machinery.
+    ///
+    /// ```
+    /// # use gen_html::{Render, html};
+    /// let mut buf = Vec::new();
+    /// html! { p { "Hello" } }.render_to_output(&mut buf).unwrap();
+    /// assert_eq!(buf, b"<p>Hello</p>");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if writing to `out` fails.
+    ///
+    /// [`IoOutput`]: crate::IoOutput
+    /// [`html!`]: crate::html
+    /// [`Formatter`]: fmt::Formatter
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        let template = render_fn(|f| self.render_to(f));
+        fmt::write(&mut OutputWriter(out), format_args!("{template}"))
+    }
+
+    /// Erases the type of the template, see [`Markup`].
+    ///
+    /// ```
+    /// # use gen_html::{Markup, Render, html};
+    /// let items: Vec<Markup> = vec![
+    ///     html! { li { "First" } }.into_markup(),
+    ///     "Second".into_markup(),
+    /// ];
+    /// # assert_eq!(items[1].render().0, "Second");
+    /// ```
+    fn into_markup(self) -> Markup
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        Markup::new(self)
     }
 }
 
@@ -68,12 +196,28 @@ impl Render for str {
     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
         escape(self, f)
     }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        escape(self, &mut OutputWriter(out))
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl Render for String {
     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.as_str().render_to(f)
     }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.as_str().render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
 }
 
 impl Render for Arguments<'_> {
@@ -83,6 +227,10 @@ impl Render for Arguments<'_> {
             None => self.to_string().render_to(f),
         }
     }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        Escaped(self).render_to_output(out)
+    }
 }
 
 impl<B> Render for Cow<'_, B>
@@ -92,6 +240,14 @@ where
     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.as_ref().render_to(f)
     }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.as_ref().render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_ref().size_hint()
+    }
 }
 
 macro_rules! ref_render_impl {
@@ -104,6 +260,14 @@ macro_rules! ref_render_impl {
                 fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
                     T::render_to(self, f)
                 }
+
+                fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+                    T::render_to_output(self, out)
+                }
+
+                fn size_hint(&self) -> usize {
+                    T::size_hint(self)
+                }
             }
         )*
     };
@@ -117,6 +281,182 @@ ref_render_impl! {
     std::sync::Arc<T>
 }
 
+/// Renders nothing for [`None`].
+impl<T: Render> Render for Option<T> {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Some(value) => value.render_to(f),
+            None => Ok(()),
+        }
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        match self {
+            Some(value) => value.render_to_output(out),
+            None => Ok(()),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, T::size_hint)
+    }
+}
+
+/// Renders the ok value, or the error according to its [`RenderError`] implementation.
+impl<T: Render, E: RenderError> Render for Result<T, E> {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ok(value) => value.render_to(f),
+            Err(err) => err.render_error(f),
+        }
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        match self {
+            Ok(value) => value.render_to_output(out),
+            Err(err) => write_display(out, &fmt::from_fn(|f| err.render_error(f))),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, T::size_hint)
+    }
+}
+
+/// Renders every element, one after another.
+impl<T: Render> Render for [T] {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.iter().try_for_each(|item| item.render_to(f))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.iter().try_for_each(|item| item.render_to_output(out))
+    }
+
+    fn size_hint(&self) -> usize {
+        self.iter().map(T::size_hint).sum()
+    }
+}
+
+impl<T: Render, const N: usize> Render for [T; N] {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.as_slice().render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_slice().size_hint()
+    }
+}
+
+impl<T: Render> Render for Vec<T> {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_slice().render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.as_slice().render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_slice().size_hint()
+    }
+}
+
+macro_rules! tuple_render_impl {
+    ( $( ( $( $t:ident )+ ) )* ) => {
+        $(
+            /// Renders every element, one after another.
+            impl<$( $t: Render ),+> Render for ( $( $t, )+ ) {
+                #[allow(non_snake_case)]
+                fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    let ( $( $t, )+ ) = self;
+                    $( $t.render_to(f)?; )+
+                    Ok(())
+                }
+
+                #[allow(non_snake_case)]
+                fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+                    let ( $( $t, )+ ) = self;
+                    $( $t.render_to_output(out)?; )+
+                    Ok(())
+                }
+
+                #[allow(non_snake_case)]
+                fn size_hint(&self) -> usize {
+                    let ( $( $t, )+ ) = self;
+                    0 $( + $t.size_hint() )+
+                }
+            }
+        )*
+    };
+}
+
+tuple_render_impl! {
+    (A)
+    (A B)
+    (A B C)
+    (A B C D)
+    (A B C D E)
+    (A B C D E F)
+    (A B C D E F G)
+    (A B C D E F G H)
+    (A B C D E F G H I)
+    (A B C D E F G H I J)
+    (A B C D E F G H I J K)
+    (A B C D E F G H I J K L)
+}
+
+/// Errors that can be rendered as part of a [`Result`].
+///
+/// [`Result<T, E>`] implements [`Render`] only when `E` implements `RenderError`, so that
+/// errors aren't rendered by accident. The default implementation makes rendering fail,
+/// like a [`Formatter`] error: [`Render::try_render`] returns [`Err`] and the web
+/// integrations respond with `500 Internal Server Error`. Override
+/// [`RenderError::render_error`] to render a fallback instead.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{RenderError, html};
+/// use std::fmt;
+///
+/// struct NotFound;
+///
+/// impl RenderError for NotFound {
+///     fn render_error(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         f.write_str("<em>Not found</em>")
+///     }
+/// }
+///
+/// let avatar: Result<&str, NotFound> = Err(NotFound);
+///
+/// let markup = html! {
+///     p { (avatar) }
+/// };
+/// # assert_eq!(markup.to_string(), "<p><em>Not found</em></p>");
+/// ```
+///
+/// [`Formatter`]: fmt::Formatter
+pub trait RenderError {
+    /// Renders the error, the default implementation returns [`Err`].
+    ///
+    /// When implementing this function, you should make sure that the output is valid HTML.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] to make rendering fail.
+    fn render_error(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let _ = f;
+        Err(fmt::Error)
+    }
+}
+
+impl RenderError for std::convert::Infallible {}
+
 macro_rules! trusted_render_impl {
     ( $( $t:ty )* ) => {
         $(
@@ -124,15 +464,196 @@ macro_rules! trusted_render_impl {
                 fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
                     write!(f, "{self}")
                 }
+
+                fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+                    write_display(out, self)
+                }
             }
         )*
     };
 }
 
+// These are formatted without `&`, `<`, `>` or `"`, so they don't need escaping.
 trusted_render_impl! {
     f32 f64
     i8 i16 i32 i64 i128 isize
     u8 u16 u32 u64 u128 usize
+    NonZeroI8 NonZeroI16 NonZeroI32 NonZeroI64 NonZeroI128 NonZeroIsize
+    NonZeroU8 NonZeroU16 NonZeroU32 NonZeroU64 NonZeroU128 NonZeroUsize
+    IpAddr Ipv4Addr Ipv6Addr
+    SocketAddr SocketAddrV4 SocketAddrV6
+}
+
+impl Render for char {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        escape(self.encode_utf8(&mut [0; 4]), f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        escape(self.encode_utf8(&mut [0; 4]), &mut OutputWriter(out))
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len_utf8()
+    }
+}
+
+/// Renders `true` or `false`.
+impl Render for bool {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if *self { "true" } else { "false" })
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        out.write_str(if *self { "true" } else { "false" })
+    }
+}
+
+/// Wrapper to render a [`Duration`] as an ISO 8601 duration, eg. `PT1M30.5S`.
+///
+/// This is a valid duration string for the `datetime` attribute of `<time>`. Hours and
+/// minutes are only written when they aren't zero, and seconds are rounded down to
+/// milliseconds.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{IsoDuration, html};
+/// use std::time::Duration;
+///
+/// let elapsed = Duration::from_millis(90_500);
+///
+/// let markup = html! {
+///     time datetime: (IsoDuration(elapsed)) { "a minute and a half" }
+/// };
+/// # assert_eq!(markup.to_string(), r#"<time datetime="PT1M30.5S">a minute and a half</time>"#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IsoDuration(pub Duration);
+
+impl fmt::Display for IsoDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let millis = self.0.subsec_millis();
+
+        f.write_str("PT")?;
+        if hours > 0 {
+            write!(f, "{hours}H")?;
+        }
+        if minutes > 0 {
+            write!(f, "{minutes}M")?;
+        }
+        if seconds > 0 || millis > 0 || secs == 0 {
+            write!(f, "{seconds}")?;
+            if millis > 0 {
+                let (mut fraction, mut width) = (millis, 3);
+                while fraction % 10 == 0 {
+                    fraction /= 10;
+                    width -= 1;
+                }
+                write!(f, ".{fraction:0width$}")?;
+            }
+            f.write_str("S")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The output never needs escaping.
+impl Render for IsoDuration {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        write_display(out, self)
+    }
+}
+
+/// Renders the path lossily, like [`Path::display`].
+impl Render for Path {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Escaped(self.display()).render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        Escaped(self.display()).render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_os_str().len()
+    }
+}
+
+impl Render for PathBuf {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_path().render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.as_path().render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_path().size_hint()
+    }
+}
+
+/// Renders the string lossily, like [`OsStr::display`].
+impl Render for OsStr {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Escaped(self.display()).render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        Escaped(self.display()).render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Render for OsString {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_os_str().render_to(f)
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        self.as_os_str().render_to_output(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_os_str().size_hint()
+    }
+}
+
+/// Returns the length of `value` if its [`Display`](fmt::Display) implementation writes
+/// it in one piece, like strings, `0` otherwise.
+///
+/// Formatting stops at the second piece, so other values aren't formatted in full.
+pub(crate) fn single_write_len<T: fmt::Display + ?Sized>(value: &T) -> usize {
+    struct Probe(Option<usize>);
+
+    impl fmt::Write for Probe {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            match self.0 {
+                Some(_) => Err(fmt::Error),
+                None => {
+                    self.0 = Some(s.len());
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    let mut probe = Probe(None);
+    match fmt::write(&mut probe, format_args!("{value}")) {
+        Ok(()) => probe.0.unwrap_or(0),
+        Err(fmt::Error) => 0,
+    }
 }
 
 /// Wrapper to render content using [`Display`] without escaping.
@@ -141,6 +662,10 @@ trusted_render_impl! {
 /// rendered without escaping. This should generally only be used for trusted content,
 /// not for user-provided input.
 ///
+/// [`Render::size_hint`] is exact for strings, and `0` for values that are formatted in
+/// several pieces.
+/// Templates implement [`Render`] themselves and don't need to be wrapped.
+///
 /// # Example
 ///
 /// ```
@@ -166,16 +691,38 @@ impl<T: fmt::Display> Render for Raw<T> {
     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.0.fmt(f)
     }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        write_display(out, &self.0)
+    }
+
+    fn size_hint(&self) -> usize {
+        single_write_len(&self.0)
+    }
 }
 
 /// Implements [`Render`] using a function.
 ///
-/// This `struct` is created by [`render_fn()`].
-pub struct RenderFn<F> {
+/// This `struct` is created by [`render_fn()`], and by the [`html!`] macro. `O` is what
+/// the function writes to: a [`Formatter`](fmt::Formatter) for `render_fn`, and
+/// `dyn` [`Output`] for templates, which skips the formatting machinery when rendering
+/// to a [`String`] or an [`Output`].
+///
+/// [`html!`]: crate::html
+pub struct RenderFn<F, O: ?Sized = fmt::Formatter<'static>> {
     f: F,
+    size_hint: usize,
+    output: PhantomData<fn(&mut O)>,
+}
+
+impl<F, O: ?Sized> RenderFn<F, O> {
+    /// Sets the value returned by [`Render::size_hint`].
+    pub fn with_size_hint(self, size_hint: usize) -> Self {
+        Self { size_hint, ..self }
+    }
 }
 
-impl<F> fmt::Debug for RenderFn<F> {
+impl<F, O: ?Sized> fmt::Debug for RenderFn<F, O> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RenderFn").finish()
     }
@@ -188,6 +735,36 @@ where
     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
         (self.f)(f)
     }
+
+    fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+}
+
+impl<F> Render for RenderFn<F, dyn Output>
+where
+    F: Fn(&mut dyn Output) -> fmt::Result,
+{
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.f)(&mut FormatterOutput(f))
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        (self.f)(out)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+}
+
+impl<F> fmt::Display for RenderFn<F, dyn Output>
+where
+    F: Fn(&mut dyn Output) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to(f)
+    }
 }
 
 impl<F> fmt::Display for RenderFn<F>
@@ -204,5 +781,143 @@ pub fn render_fn<F>(f: F) -> RenderFn<F>
 where
     F: Fn(&mut fmt::Formatter) -> fmt::Result,
 {
-    RenderFn { f }
+    RenderFn {
+        f,
+        size_hint: 0,
+        output: PhantomData,
+    }
+}
+
+/// Creates a [`RenderFn`] from a function writing to an [`Output`], used by the
+/// [`html!`] macro.
+///
+/// [`html!`]: crate::html
+#[doc(hidden)]
+pub fn output_fn<F>(f: F) -> RenderFn<F, dyn Output>
+where
+    F: Fn(&mut dyn Output) -> fmt::Result,
+{
+    RenderFn {
+        f,
+        size_hint: 0,
+        output: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IsoDuration, Raw, Render, RenderError, render_fn};
+    use crate::{Escaped, value::render_value_to};
+    use std::fmt;
+
+    #[test]
+    fn fallible() {
+        let failing = render_fn(|f| {
+            f.write_str("partial")?;
+            Err(fmt::Error)
+        });
+
+        assert!(failing.try_render().is_err());
+
+        let mut buf = String::new();
+        assert!(failing.render_into(&mut buf).is_err());
+        assert_eq!(buf, "partial");
+
+        let err = failing.write_to(Vec::new()).unwrap_err();
+        assert_eq!(err.to_string(), "render_to returned Err");
+    }
+
+    #[test]
+    fn render_into_appends() {
+        let mut buf = String::from("<ul>");
+        "<li>".render_into(&mut buf).unwrap();
+        assert_eq!(buf, "<ul>&lt;li&gt;");
+
+        let mut bytes = Vec::new();
+        String::from("a & b").write_to(&mut bytes).unwrap();
+        assert_eq!(bytes, b"a &amp; b");
+    }
+
+    #[test]
+    fn wrapper_size_hints() {
+        assert_eq!(Raw("<br>").size_hint(), 4);
+        assert_eq!(Raw(String::from("<hr>")).size_hint(), 4);
+        assert_eq!(Raw(123).size_hint(), 3);
+        let n = 12;
+        assert_eq!(Raw(format_args!("{n}-ab")).size_hint(), 0);
+        assert_eq!(Escaped("a & b").size_hint(), 5);
+    }
+
+    #[test]
+    fn collections() {
+        assert_eq!(Some("<b>").render().0, "&lt;b&gt;");
+        assert_eq!(None::<&str>.render().0, "");
+        assert_eq!(vec!["a", "&"].render().0, "a&amp;");
+        assert_eq!([1, 2, 3].render().0, "123");
+        assert_eq!(("a", 1, Some('b'.to_string())).render().0, "a1b");
+        assert_eq!(vec![Some("a"); 2].size_hint(), 2);
+    }
+
+    #[test]
+    fn results() {
+        struct Failing;
+        impl RenderError for Failing {}
+
+        assert_eq!(Ok::<_, Failing>("ok").render().0, "ok");
+        assert!(Err::<&str, _>(Failing).try_render().is_err());
+    }
+
+    #[test]
+    fn optional_values() {
+        let mut out = String::new();
+        render_value_to(&Some(None::<&str>), "omitted", &mut out).unwrap();
+        render_value_to(&Some(Some("x")), "nested", &mut out).unwrap();
+        render_value_to(&[Some("a"), None], "list", &mut out).unwrap();
+
+        assert_eq!(out, r#" nested="x" list="a""#);
+    }
+
+    #[test]
+    fn std_types() {
+        use std::{
+            ffi::OsStr,
+            net::{Ipv6Addr, SocketAddr},
+            num::NonZeroU8,
+            path::Path,
+            time::Duration,
+        };
+
+        assert_eq!('<'.render().0, "&lt;");
+        assert_eq!('ż'.render().0, "ż");
+        assert_eq!(true.render().0, "true");
+        assert_eq!(NonZeroU8::MIN.render().0, "1");
+        assert_eq!(Ipv6Addr::LOCALHOST.render().0, "::1");
+        let addr: SocketAddr = "[::1]:8080".parse().unwrap();
+        assert_eq!(addr.render().0, "[::1]:8080");
+        assert_eq!(
+            IsoDuration(Duration::from_millis(1500)).render().0,
+            "PT1.5S"
+        );
+        assert_eq!(IsoDuration(Duration::from_secs(3600)).render().0, "PT1H");
+        assert_eq!(
+            IsoDuration(Duration::from_millis(3_723_040)).render().0,
+            "PT1H2M3.04S"
+        );
+        assert_eq!(
+            IsoDuration(Duration::from_nanos(999_999)).render().0,
+            "PT0S"
+        );
+        assert_eq!(Path::new("a&b/<c>").render().0, "a&amp;b/&lt;c&gt;");
+        assert_eq!(OsStr::new("\"q\"").render().0, "&quot;q&quot;");
+    }
+
+    #[test]
+    fn bool_values() {
+        let mut out = String::new();
+        render_value_to(&true, "checked", &mut out).unwrap();
+        render_value_to(&false, "hidden", &mut out).unwrap();
+        render_value_to(&Some(true), "open", &mut out).unwrap();
+
+        assert_eq!(out, " checked open");
+    }
 }
@@ -0,0 +1,87 @@
+use crate::{Output, Render, render::output_fn};
+use std::fmt;
+
+/// Content of a named slot of a [component].
+///
+/// Slot parameters of a component are marked with `#[prop(slot)]`. They are optional,
+/// an unfilled slot renders nothing. Use [`Slot::or`] to render a fallback instead.
+///
+/// # Example
+///
+/// ```
+/// use gen_html::{Render, Slot, component, html};
+///
+/// #[component]
+/// fn layout(#[prop(slot)] header: Slot, children: impl Render) -> impl Render {
+///     html! {
+///         header { (header.or("Untitled")) }
+///         main { (children) }
+///     }
+/// }
+///
+/// let markup = html! {
+///     Layout {
+///         slot header { h1 { "Home" } }
+///         p { "Welcome!" }
+///     }
+///     Layout { "No header" }
+/// };
+/// # assert_eq!(
+/// #     markup.to_string(),
+/// #     "<header><h1>Home</h1></header><main><p>Welcome!</p></main>\
+/// #      <header>Untitled</header><main>No header</main>"
+/// # );
+/// ```
+///
+/// [component]: crate::component
+#[derive(Clone, Copy, Default)]
+pub struct Slot<'a>(Option<&'a dyn Render>);
+
+impl<'a> Slot<'a> {
+    /// Returns `true` if the caller filled this slot.
+    pub fn is_filled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Renders the content of this slot, or `fallback` if it wasn't filled.
+    pub fn or<R: Render>(self, fallback: R) -> impl Render + use<'a, R> {
+        output_fn(move |out| match self.0 {
+            Some(content) => content.render_to_output(out),
+            None => fallback.render_to_output(out),
+        })
+    }
+}
+
+impl fmt::Debug for Slot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slot")
+            .field("filled", &self.is_filled())
+            .finish()
+    }
+}
+
+impl<'a, R: Render> From<&'a R> for Slot<'a> {
+    fn from(content: &'a R) -> Self {
+        Self(Some(content))
+    }
+}
+
+impl Render for Slot<'_> {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(content) => content.render_to(f),
+            None => Ok(()),
+        }
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        match self.0 {
+            Some(content) => content.render_to_output(out),
+            None => Ok(()),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.map_or(0, Render::size_hint)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Output, Render};
+use std::fmt;
+
+gen_html_proc::__tag_enum! {
+    /// An HTML element name, for elements whose name is picked at runtime.
+    ///
+    /// Use `tag (expr)` instead of an element name inside of the [`html!`] macro.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gen_html::{Render, Tag, component, html};
+    ///
+    /// #[component]
+    /// fn heading(level: u8, children: impl Render) -> impl Render {
+    ///     let tag = match level {
+    ///         1 => Tag::H1,
+    ///         2 => Tag::H2,
+    ///         _ => Tag::H3,
+    ///     };
+    ///
+    ///     html! {
+    ///         tag (tag) ."heading" { (children) }
+    ///     }
+    /// }
+    ///
+    /// let markup = html! {
+    ///     Heading level: (2) { "Getting started" }
+    /// };
+    /// # assert_eq!(
+    /// #     markup.to_string(),
+    /// #     r#"<h2 class="heading">Getting started</h2>"#
+    /// # );
+    /// ```
+    ///
+    /// # Void elements
+    ///
+    /// Void elements, like [`Tag::Img`] or [`Tag::Br`], have no children and no closing
+    /// tag. `tag (expr) { ... }` with a void element renders only the opening tag, the body
+    /// is skipped without being evaluated.
+    ///
+    /// ```
+    /// # use gen_html::{Tag, html};
+    /// let tag = Tag::Img;
+    ///
+    /// let markup = html! {
+    ///     tag (tag) src: "logo.png" {
+    ///         "not rendered"
+    ///     }
+    /// };
+    /// # assert_eq!(markup.to_string(), r#"<img src="logo.png">"#);
+    /// ```
+    ///
+    /// [`html!`]: crate::html
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum Tag;
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Render for Tag {
+    fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+
+    fn render_to_output(&self, out: &mut dyn Output) -> fmt::Result {
+        out.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tag;
+
+    #[test]
+    fn names() {
+        assert_eq!(Tag::Figcaption.as_str(), "figcaption");
+        assert_eq!(Tag::from_name("h1"), Some(Tag::H1));
+        assert_eq!(Tag::from_name("blink"), None);
+        assert!(Tag::Img.is_void());
+        assert!(!Tag::Div.is_void());
+    }
+}
@@ -1,5 +1,22 @@
-use crate::Render;
-use std::fmt::{self, Write};
+use crate::{
+    Attributes, Escaped, Fragment, FragmentFn, IsoDuration, LocalMarkup, Markup, Output, Raw,
+    Render, RenderEach, RenderError, RenderFn, RenderJoined, RenderOnce, Slot, Tag,
+    class::ClassList,
+};
+use private::Token;
+use std::{
+    borrow::Cow,
+    ffi::{OsStr, OsString},
+    fmt::{self, Arguments},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::{
+        NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+    },
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+};
 
 /// Types that can be used as attribute values in the [`html!`] macro.
 ///
@@ -11,7 +28,12 @@ use std::fmt::{self, Write};
 /// | [`Some<T>`] |                      ✓ |
 /// | [`None`]    |                      ✗ |
 ///
-/// `where T: Render`
+/// `where T: Value`
+///
+/// `Value` is implemented for the [`Render`] types of this crate and the standard
+/// library. Other types can be used as attribute values by implementing `Value` for
+/// them. The implementation is always empty, the value is written with its [`Render`]
+/// implementation between quotes.
 ///
 /// # Examples
 ///
@@ -44,80 +66,305 @@ use std::fmt::{self, Write};
 /// assert_eq!(markup.render().0, r#"<button data-cooldown="200ms">click me</button>"#);
 /// ```
 ///
+/// Implementing `Value` for your own type:
+///
+/// ```
+/// use gen_html::{Render, Value, html};
+/// use std::fmt;
+///
+/// struct Color(u8, u8, u8);
+///
+/// impl Render for Color {
+///     fn render_to(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+///     }
+/// }
+///
+/// impl Value for Color {}
+///
+/// let markup = html! {
+///     input r#type: "color" value: (Color(255, 0, 0));
+/// };
+///
+/// assert_eq!(markup.render().0, r##"<input type="color" value="#ff0000">"##);
+/// ```
+///
 /// [`html!`]: crate::html
-pub trait Value: private::Sealed {
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as an attribute value",
+    note = "`Render` types can be used as attribute values by implementing `Value` for them"
+)]
+pub trait Value: Render {
+    /// Writes the attribute called `name`, ` name="value"` by default.
+    #[doc(hidden)]
+    fn render_value_to(&self, name: &str, out: &mut dyn Output, _: Token) -> fmt::Result {
+        write_value(self, name, out)
+    }
+
     #[doc(hidden)]
-    fn render_value_to(&self, name: &str, f: &mut fmt::Formatter) -> fmt::Result;
+    fn insert_into(&self, name: Cow<'static, str>, attrs: &mut Attributes, _: Token) {
+        attrs.insert_escaped(name, Some(self.render().0));
+    }
+
+    /// Renders the value, `None` if the attribute is omitted or has no value.
+    #[doc(hidden)]
+    fn render_value(&self, _: Token) -> Option<String> {
+        Some(self.render().0)
+    }
 }
 
+/// Writes the attribute `name` with `value`, used by code generated with [`html!`].
+///
+/// [`html!`]: crate::html
+pub fn render_value_to<V>(value: &V, name: &str, out: &mut dyn Output) -> fmt::Result
+where
+    V: Value + ?Sized,
+{
+    value.render_value_to(name, out, Token)
+}
+
+/// Inserts the attribute `name` with `value` into `attrs`, used by code generated with
+/// [`html!`] for elements with spread attributes.
+///
+/// [`html!`]: crate::html
+pub fn insert_value<V>(value: &V, name: Cow<'static, str>, attrs: &mut Attributes)
+where
+    V: Value + ?Sized,
+{
+    value.insert_into(name, attrs, Token);
+}
+
+/// Renders `value`, `None` if the attribute is omitted or has no value.
+pub(crate) fn render_value<V: Value + ?Sized>(value: &V) -> Option<String> {
+    value.render_value(Token)
+}
+
+/// Writes ` name="value"`.
+fn write_value<V: Render + ?Sized>(value: &V, name: &str, out: &mut dyn Output) -> fmt::Result {
+    out.write_str(" ")?;
+    out.write_str(name)?;
+    out.write_str("=\"")?;
+    value.render_to_output(out)?;
+    out.write_str("\"")
+}
+
+/// `true` renders a boolean attribute, `false` omits it.
 impl Value for bool {
-    fn render_value_to(&self, name: &str, f: &mut fmt::Formatter) -> fmt::Result {
-        if *self { write!(f, " {name}") } else { Ok(()) }
+    fn render_value_to(&self, name: &str, out: &mut dyn Output, _: Token) -> fmt::Result {
+        if *self {
+            out.write_str(" ")?;
+            out.write_str(name)?;
+        }
+        Ok(())
+    }
+
+    fn insert_into(&self, name: Cow<'static, str>, attrs: &mut Attributes, _: Token) {
+        if *self {
+            attrs.insert_escaped(name, None);
+        }
+    }
+
+    fn render_value(&self, _: Token) -> Option<String> {
+        None
     }
 }
 
-impl<R: Render> Value for Option<R> {
-    fn render_value_to(&self, name: &str, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(r) = self {
-            write!(f, " {name}=\"")?;
-            r.render_to(f)?;
-            f.write_char('"')?;
+/// [`None`] omits the attribute.
+impl<T: Value> Value for Option<T> {
+    fn render_value_to(&self, name: &str, out: &mut dyn Output, _: Token) -> fmt::Result {
+        match self {
+            Some(value) => value.render_value_to(name, out, Token),
+            None => Ok(()),
         }
+    }
 
-        Ok(())
+    fn insert_into(&self, name: Cow<'static, str>, attrs: &mut Attributes, _: Token) {
+        if let Some(value) = self {
+            value.insert_into(name, attrs, Token);
+        }
+    }
+
+    fn render_value(&self, _: Token) -> Option<String> {
+        self.as_ref().and_then(|value| value.render_value(Token))
     }
 }
 
-impl<R: Render> Value for R {
-    fn render_value_to(&self, name: &str, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, " {name}=\"")?;
-        self.render_to(f)?;
-        f.write_char('"')?;
+/// The error is rendered as the value, see [`RenderError`].
+impl<T: Value, E: RenderError> Value for Result<T, E> {
+    fn render_value_to(&self, name: &str, out: &mut dyn Output, _: Token) -> fmt::Result {
+        match self {
+            Ok(value) => value.render_value_to(name, out, Token),
+            Err(_) => write_value(self, name, out),
+        }
+    }
 
-        Ok(())
+    fn insert_into(&self, name: Cow<'static, str>, attrs: &mut Attributes, _: Token) {
+        match self {
+            Ok(value) => value.insert_into(name, attrs, Token),
+            Err(_) => attrs.insert_escaped(name, Some(self.render().0)),
+        }
+    }
+
+    fn render_value(&self, _: Token) -> Option<String> {
+        match self {
+            Ok(value) => value.render_value(Token),
+            Err(_) => Some(self.render().0),
+        }
     }
 }
 
-mod private {
-    pub trait Sealed {}
+impl<B> Value for Cow<'_, B>
+where
+    B: Value + ToOwned + ?Sized,
+{
+    fn render_value_to(&self, name: &str, out: &mut dyn Output, _: Token) -> fmt::Result {
+        self.as_ref().render_value_to(name, out, Token)
+    }
+
+    fn insert_into(&self, name: Cow<'static, str>, attrs: &mut Attributes, _: Token) {
+        self.as_ref().insert_into(name, attrs, Token);
+    }
+
+    fn render_value(&self, _: Token) -> Option<String> {
+        self.as_ref().render_value(Token)
+    }
+}
+
+macro_rules! ref_value_impl {
+    ( $( $t:ty )* ) => {
+        $(
+            impl<T> Value for $t
+            where
+                T: Value + ?Sized,
+            {
+                fn render_value_to(&self, name: &str, out: &mut dyn Output, _: Token) -> fmt::Result {
+                    T::render_value_to(self, name, out, Token)
+                }
+
+                fn insert_into(&self, name: Cow<'static, str>, attrs: &mut Attributes, _: Token) {
+                    T::insert_into(self, name, attrs, Token);
+                }
+
+                fn render_value(&self, _: Token) -> Option<String> {
+                    T::render_value(self, Token)
+                }
+            }
+        )*
+    };
+}
+
+ref_value_impl! {
+    &T
+    &mut T
+    Box<T>
+    Rc<T>
+    Arc<T>
+}
 
-    impl<T: crate::Value> Sealed for T {}
+macro_rules! value_impl {
+    ( $( $t:ty )* ) => {
+        $(
+            impl Value for $t {}
+        )*
+    };
+}
+
+value_impl! {
+    str String Arguments<'_> char
+    f32 f64
+    i8 i16 i32 i64 i128 isize
+    u8 u16 u32 u64 u128 usize
+    NonZeroI8 NonZeroI16 NonZeroI32 NonZeroI64 NonZeroI128 NonZeroIsize
+    NonZeroU8 NonZeroU16 NonZeroU32 NonZeroU64 NonZeroU128 NonZeroUsize
+    IpAddr Ipv4Addr Ipv6Addr
+    SocketAddr SocketAddrV4 SocketAddrV6
+    Path PathBuf OsStr OsString
+    Markup LocalMarkup<'_> Slot<'_> Tag IsoDuration
+}
+
+macro_rules! tuple_value_impl {
+    ( $( ( $( $t:ident )+ ) )* ) => {
+        $(
+            impl<$( $t: Render ),+> Value for ( $( $t, )+ ) {}
+        )*
+    };
+}
+
+tuple_value_impl! {
+    (A)
+    (A B)
+    (A B C)
+    (A B C D)
+    (A B C D E)
+    (A B C D E F)
+    (A B C D E F G)
+    (A B C D E F G H)
+    (A B C D E F G H I)
+    (A B C D E F G H I J)
+    (A B C D E F G H I J K)
+    (A B C D E F G H I J K L)
+}
+
+impl<T: Render> Value for [T] {}
+
+impl<T: Render, const N: usize> Value for [T; N] {}
+
+impl<T: Render> Value for Vec<T> {}
+
+impl<T: fmt::Display> Value for Raw<T> {}
+
+impl<T: fmt::Display> Value for Escaped<T> {}
+
+impl<const N: usize> Value for ClassList<N> {}
+
+impl<F, O: ?Sized> Value for RenderFn<F, O> where Self: Render {}
+
+impl<F> Value for RenderOnce<F> where Self: Render {}
+
+impl<F> Value for FragmentFn<F> where Self: Render {}
+
+impl<F> Value for Fragment<'_, F> where Self: Render {}
+
+impl<I, F> Value for RenderEach<I, F> where Self: Render {}
+
+impl<I, S> Value for RenderJoined<I, S> where Self: Render {}
+
+/// Keeps the methods of [`Value`] from being overridden outside of this crate, so that
+/// attribute values are always escaped.
+mod private {
+    pub struct Token;
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Value;
-    use std::fmt;
+    use super::render_value_to;
 
     #[test]
     fn bool_attributes() {
-        let display = fmt::from_fn(|f| {
-            false.render_value_to("checked", f).unwrap();
-            true.render_value_to("some-attribute", f)
-        });
+        let mut out = String::new();
+        render_value_to(&false, "checked", &mut out).unwrap();
+        render_value_to(&true, "some-attribute", &mut out).unwrap();
 
-        assert_eq!(display.to_string(), " some-attribute");
+        assert_eq!(out, " some-attribute");
     }
 
     #[test]
     fn optional_attributes() {
-        let display = fmt::from_fn(|f| {
-            None::<i32>.render_value_to("hello-world", f).unwrap();
-            Some("escape this\"<").render_value_to("attr-123", f)
-        });
+        let mut out = String::new();
+        render_value_to(&None::<i32>, "hello-world", &mut out).unwrap();
+        render_value_to(&Some("escape this\"<"), "attr-123", &mut out).unwrap();
 
-        assert_eq!(display.to_string(), r#" attr-123="escape this&quot;&lt;""#);
+        assert_eq!(out, r#" attr-123="escape this&quot;&lt;""#);
     }
 
     #[test]
     fn normal_attributes() {
-        let display = fmt::from_fn(|f| {
-            "&".render_value_to("hello-world", f).unwrap();
-            "escape this\"<".render_value_to("attr-123", f)
-        });
+        let mut out = String::new();
+        render_value_to(&"&", "hello-world", &mut out).unwrap();
+        render_value_to(&"escape this\"<", "attr-123", &mut out).unwrap();
 
         assert_eq!(
-            display.to_string(),
+            out,
             r#" hello-world="&amp;" attr-123="escape this&quot;&lt;""#
         );
     }
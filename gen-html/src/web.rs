@@ -1,28 +1,90 @@
+#[cfg(feature = "axum")]
+pub(crate) use self::axum::respond_with;
+
 #[cfg(feature = "axum")]
 #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
 mod axum {
-    use crate::{Escaped, Raw, Render, RenderFn};
-    use axum::response::{Html, IntoResponse, Response};
+    use crate::{
+        Escaped, Fragment, FragmentFn, LocalMarkup, Markup, Output, Raw, Render, RenderFn,
+        RenderOnce,
+    };
+    use axum::{
+        http::StatusCode,
+        response::{Html, IntoResponse, Response},
+    };
     use std::fmt;
 
+    /// Responds with the rendered HTML, or `500 Internal Server Error` if rendering fails.
+    fn respond(html: &impl Render) -> Response {
+        let mut buf = String::with_capacity(html.size_hint());
+        respond_with(html.render_into(&mut buf).map(|()| Raw(buf)))
+    }
+
+    /// Responds with already rendered HTML, see [`respond`].
+    pub(crate) fn respond_with(html: Result<Raw<String>, fmt::Error>) -> Response {
+        match html {
+            Ok(html) => Html(html.0).into_response(),
+            Err(fmt::Error) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+
     impl<T: fmt::Display> IntoResponse for Escaped<T> {
         fn into_response(self) -> Response {
-            self.render().into_response()
+            respond(&self)
         }
     }
 
     impl<T: fmt::Display> IntoResponse for Raw<T> {
         fn into_response(self) -> Response {
-            Html(self.0.to_string()).into_response()
+            respond(&self)
+        }
+    }
+
+    impl IntoResponse for Markup {
+        fn into_response(self) -> Response {
+            respond(&self)
+        }
+    }
+
+    impl IntoResponse for LocalMarkup<'_> {
+        fn into_response(self) -> Response {
+            respond(&self)
+        }
+    }
+
+    impl<F, O: ?Sized> IntoResponse for RenderFn<F, O>
+    where
+        Self: Render,
+    {
+        fn into_response(self) -> Response {
+            respond(&self)
+        }
+    }
+
+    impl<F> IntoResponse for RenderOnce<F>
+    where
+        F: FnOnce(&mut dyn Output) -> fmt::Result,
+    {
+        fn into_response(self) -> Response {
+            respond(&self)
         }
     }
 
-    impl<F> IntoResponse for RenderFn<F>
+    impl<F> IntoResponse for FragmentFn<F>
     where
-        F: Fn(&mut fmt::Formatter) -> fmt::Result,
+        F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
     {
         fn into_response(self) -> Response {
-            self.render().into_response()
+            respond(&self)
+        }
+    }
+
+    impl<F> IntoResponse for Fragment<'_, F>
+    where
+        F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+    {
+        fn into_response(self) -> Response {
+            respond(&self)
         }
     }
 }
@@ -30,15 +92,29 @@ mod axum {
 #[cfg(feature = "actix-web")]
 #[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
 mod actix_web {
-    use crate::{Escaped, Raw, Render, RenderFn};
-    use actix_web::{HttpRequest, HttpResponse, Responder, web::Html};
+    use crate::{
+        Escaped, Fragment, FragmentFn, LocalMarkup, Markup, Output, Raw, Render, RenderFn,
+        RenderOnce,
+    };
+    use actix_web::{HttpRequest, HttpResponse, Responder, http::StatusCode, web::Html};
     use std::fmt;
 
+    /// Responds with the rendered HTML, or `500 Internal Server Error` if rendering fails.
+    fn respond(html: &impl Render, req: &HttpRequest) -> HttpResponse<String> {
+        let mut buf = String::with_capacity(html.size_hint());
+        match html.render_into(&mut buf) {
+            Ok(()) => Html::new(buf).respond_to(req),
+            Err(fmt::Error) => {
+                HttpResponse::with_body(StatusCode::INTERNAL_SERVER_ERROR, String::new())
+            }
+        }
+    }
+
     impl<T: fmt::Display> Responder for Escaped<T> {
         type Body = String;
 
         fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
-            self.render().respond_to(req)
+            respond(&self, req)
         }
     }
 
@@ -46,18 +122,67 @@ mod actix_web {
         type Body = String;
 
         fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
-            Html::new(self.0.to_string()).respond_to(req)
+            respond(&self, req)
+        }
+    }
+
+    impl Responder for Markup {
+        type Body = String;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            respond(&self, req)
+        }
+    }
+
+    impl Responder for LocalMarkup<'_> {
+        type Body = String;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            respond(&self, req)
+        }
+    }
+
+    impl<F, O: ?Sized> Responder for RenderFn<F, O>
+    where
+        Self: Render,
+    {
+        type Body = String;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            respond(&self, req)
+        }
+    }
+
+    impl<F> Responder for RenderOnce<F>
+    where
+        F: FnOnce(&mut dyn Output) -> fmt::Result,
+    {
+        type Body = String;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            respond(&self, req)
+        }
+    }
+
+    impl<F> Responder for FragmentFn<F>
+    where
+        F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
+    {
+        type Body = String;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            respond(&self, req)
         }
     }
 
-    impl<F> Responder for RenderFn<F>
+    impl<F> Responder for Fragment<'_, F>
     where
-        F: Fn(&mut fmt::Formatter) -> fmt::Result,
+        F: Fn(&mut dyn Output, Option<&str>) -> fmt::Result,
     {
         type Body = String;
 
         fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
-            self.render().respond_to(req)
+            respond(&self, req)
         }
     }
 }